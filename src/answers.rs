@@ -0,0 +1,161 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Accepted answers for the real input, stored in `years/<year>/answers/NN.toml` by default:
+//!
+//! ```toml
+//! part1 = 24000
+//! part2 = "45000"
+//! ```
+//!
+//! Multi-line answers can use `"""` strings. Either key may be left out.
+use crate::runner::PartResult;
+use crate::toml;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect { expected: String },
+}
+
+impl Answers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Compares a result with the stored answer. Returns `None` if there is no stored answer.
+    /// A part that is not solved but has a stored answer is incorrect.
+    pub fn check(&self, result: &PartResult) -> Option<Verdict> {
+        let expected = self.get(result.part)?;
+        let correct = result
+            .answer()
+            .is_some_and(|answer| answer.trim() == expected.trim());
+
+        Some(if correct {
+            Verdict::Correct
+        } else {
+            Verdict::Incorrect {
+                expected: expected.into(),
+            }
+        })
+    }
+}
+
+pub fn answers_path(year: u16, day: u8) -> PathBuf {
+    crate::config::get()
+        .dir("answers", year)
+        .join(format!("{:02}.toml", day))
+}
+
+/// Reads the answers file for `day`. Returns `Ok(None)` when the day has no answers file.
+pub fn read(year: u16, day: u8) -> Result<Option<Answers>, String> {
+    let path = answers_path(year, day);
+    match fs::read_to_string(&path) {
+        Ok(contents) => parse(&contents)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+/// Stores an accepted answer for a part, unless the answers file already has one.
+pub fn record(year: u16, day: u8, part: u8, answer: &str) -> Result<(), String> {
+    let path = answers_path(year, day);
+    if read(year, day)?.is_some_and(|answers| answers.get(part).is_some()) {
+        return Ok(());
+    }
+
+    let mut contents = fs::read_to_string(&path).unwrap_or_default();
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&format!("part{} = {}\n", part, toml::quote(answer)));
+
+    fs::create_dir_all(path.parent().unwrap())
+        .and_then(|_| fs::write(&path, contents))
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn parse(contents: &str) -> Result<Answers, String> {
+    let mut answers = Answers::default();
+
+    for (key, value) in toml::parse(contents)? {
+        match key.as_str() {
+            "part1" => answers.part_one = Some(value),
+            "part2" => answers.part_two = Some(value),
+            other => return Err(format!("unknown key \"{}\".", other)),
+        }
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::Outcome;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse() {
+        let answers = parse("# Day 1\npart1 = 24000\npart2 = \"45000\" # accepted\n").unwrap();
+        assert_eq!(answers.get(1), Some("24000"));
+        assert_eq!(answers.get(2), Some("45000"));
+
+        let partial = parse("part2 = \"\\\"quoted\\\"\"").unwrap();
+        assert_eq!(partial.part_one, None);
+        assert_eq!(partial.get(2), Some("\"quoted\""));
+    }
+
+    #[test]
+    fn test_parse_multiline() {
+        let answers = parse("part1 = 1\npart2 = \"\"\"\n##..\n#..#\n\"\"\"\n").unwrap();
+        assert_eq!(answers.get(2), Some("##..\n#..#\n"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("part3 = 1").is_err());
+        assert!(parse("part1 = abc").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = parse("part1 = 24000\n").unwrap();
+        let result = |part: u8, outcome: Outcome| PartResult {
+            day: 1,
+            part,
+            outcome,
+            elapsed: Duration::ZERO,
+            bench: None,
+            verdict: None,
+        };
+
+        assert_eq!(
+            answers.check(&result(1, Outcome::Solved("24000".into()))),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            answers.check(&result(1, Outcome::Unsolved)),
+            Some(Verdict::Incorrect {
+                expected: "24000".into()
+            })
+        );
+        assert_eq!(answers.check(&result(2, Outcome::Solved("1".into()))), None);
+    }
+}
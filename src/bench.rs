@@ -0,0 +1,455 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::runner::{Outcome, PartResult};
+use crate::{ansi, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::time::{Duration, Instant};
+
+pub const BENCH_FLAG: &str = "--bench";
+
+const DEFAULT_WARMUP: u32 = 3;
+const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchOptions {
+    /// Calls made before measuring starts. Their timings are discarded.
+    pub warmup: u32,
+    /// Exact number of measured calls. Overrides `budget` when set.
+    pub runs: Option<u32>,
+    /// Measure until this much time has been spent. At least one call is always measured.
+    pub budget: Duration,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: DEFAULT_WARMUP,
+            runs: None,
+            budget: DEFAULT_BUDGET,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Reads `--bench`, `--warmup <n>`, `--runs <n>` and `--bench-time <seconds>` from the
+    /// command line. Returns `None` when `--bench` was not passed.
+    pub fn from_args() -> Result<Option<Self>, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_env();
+        if !args.contains(BENCH_FLAG) {
+            return Ok(None);
+        }
+
+        let defaults = BenchOptions::default();
+        Ok(Some(BenchOptions {
+            warmup: args
+                .opt_value_from_str("--warmup")?
+                .unwrap_or(defaults.warmup),
+            runs: args.opt_value_from_str("--runs")?,
+            budget: args
+                .opt_value_from_fn("--bench-time", |s| {
+                    s.parse::<f64>()
+                        .ok()
+                        .filter(|secs| *secs >= 0_f64)
+                        .map(Duration::from_secs_f64)
+                        .ok_or("expected a number of seconds")
+                })?
+                .unwrap_or(defaults.budget),
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub runs: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl BenchStats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean_ns = sorted.iter().map(|d| d.as_nanos() as f64).sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            sorted
+                .iter()
+                .map(|d| (d.as_nanos() as f64 - mean_ns).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0_f64
+        };
+
+        Some(BenchStats {
+            runs: n as u32,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean_ns.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+/// Calls `func` repeatedly according to `options` and returns timing statistics.
+pub fn bench<T>(
+    func: impl Fn(&str) -> Option<T>,
+    input: &str,
+    options: &BenchOptions,
+) -> BenchStats {
+    for _ in 0..options.warmup {
+        std::hint::black_box(func(std::hint::black_box(input)));
+    }
+
+    let mut samples = vec![];
+    let started = Instant::now();
+
+    loop {
+        let timer = Instant::now();
+        std::hint::black_box(func(std::hint::black_box(input)));
+        samples.push(timer.elapsed());
+
+        let done = match options.runs {
+            Some(runs) => samples.len() >= runs.max(1) as usize,
+            None => started.elapsed() >= options.budget,
+        };
+        if done {
+            break;
+        }
+    }
+
+    BenchStats::from_samples(&samples).unwrap()
+}
+
+pub fn print_stats(stats: &BenchStats) {
+    println!(
+        "{}(bench: {} runs, min {:.2?}, median {:.2?}, mean {:.2?} ± {:.2?}){}",
+        ansi(ANSI_ITALIC),
+        stats.runs,
+        stats.min,
+        stats.median,
+        stats.mean,
+        stats.stddev,
+        ansi(ANSI_RESET)
+    );
+}
+
+/// Prints one row per part. Parts without statistics show why they were not benchmarked.
+pub fn print_table(results: &[PartResult]) {
+    let header = format!(
+        "{:>3} | {:>4} | {:>6} | {:>10} | {:>10} | {:>10} | {:>10}",
+        "Day", "Part", "Runs", "Min", "Median", "Mean", "Stddev"
+    );
+    println!("{}{}{}", ansi(ANSI_BOLD), header, ansi(ANSI_RESET));
+    println!("{}", "-".repeat(header.len()));
+
+    for result in results {
+        match (&result.outcome, &result.bench) {
+            (Outcome::Solved(_), Some(stats)) => println!(
+                "{:>3} | {:>4} | {:>6} | {:>10} | {:>10} | {:>10} | {:>10}",
+                result.day,
+                result.part,
+                stats.runs,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.stddev),
+            ),
+            (outcome, _) => println!(
+                "{:>3} | {:>4} | {}",
+                result.day,
+                result.part,
+                match outcome {
+                    Outcome::Failed(_) => "failed",
+                    _ => "not solved",
+                }
+            ),
+        }
+    }
+}
+
+/// Benchmark results recorded across runs, one JSON line per day/part in `target/aoc-bench.jsonl`.
+pub mod history {
+    use super::BenchStats;
+    use crate::json;
+    use crate::runner::PartResult;
+    use std::fs::{self, OpenOptions};
+    use std::io::{self, Write};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    /// A part is flagged when its median got slower than this, in percent.
+    pub const DEFAULT_THRESHOLD: f64 = 10_f64;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Entry {
+        pub commit: String,
+        /// Seconds since the unix epoch.
+        pub timestamp: u64,
+        pub year: u16,
+        pub day: u8,
+        pub part: u8,
+        pub stats: BenchStats,
+    }
+
+    impl Entry {
+        pub fn to_json(&self) -> String {
+            json::object(&[
+                ("commit", json::escape(&self.commit)),
+                ("timestamp", self.timestamp.to_string()),
+                ("year", self.year.to_string()),
+                ("day", self.day.to_string()),
+                ("part", self.part.to_string()),
+                ("runs", self.stats.runs.to_string()),
+                ("min_ns", self.stats.min.as_nanos().to_string()),
+                ("median_ns", self.stats.median.as_nanos().to_string()),
+                ("mean_ns", self.stats.mean.as_nanos().to_string()),
+                ("stddev_ns", self.stats.stddev.as_nanos().to_string()),
+            ])
+        }
+
+        pub fn from_json(line: &str) -> Option<Self> {
+            let object = json::parse_object(line)?;
+            let nanos = |key: &str| object.get(key)?.parse_number().map(Duration::from_nanos);
+
+            Some(Entry {
+                commit: object.get("commit")?.as_str()?.into(),
+                timestamp: object.get("timestamp")?.parse_number()?,
+                year: object.get("year")?.parse_number()?,
+                day: object.get("day")?.parse_number()?,
+                part: object.get("part")?.parse_number()?,
+                stats: BenchStats {
+                    runs: object.get("runs")?.parse_number()?,
+                    min: nanos("min_ns")?,
+                    median: nanos("median_ns")?,
+                    mean: nanos("mean_ns")?,
+                    stddev: nanos("stddev_ns")?,
+                },
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Regression {
+        pub day: u8,
+        pub part: u8,
+        pub previous: Entry,
+        pub current: BenchStats,
+        /// How much slower the median got, in percent.
+        pub slowdown: f64,
+    }
+
+    pub fn default_path() -> PathBuf {
+        crate::project_root().join("target").join("aoc-bench.jsonl")
+    }
+
+    /// Short hash of `HEAD`, suffixed with `-dirty` for uncommitted changes.
+    pub fn current_commit() -> String {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        match git(&["rev-parse", "--short", "HEAD"]) {
+            Some(hash) => match git(&["status", "--porcelain"]) {
+                Some(status) if !status.is_empty() => format!("{}-dirty", hash),
+                _ => hash,
+            },
+            None => "unknown".into(),
+        }
+    }
+
+    /// Reads all entries, skipping lines that can't be parsed. A missing file is an empty history.
+    pub fn read(path: &Path) -> io::Result<Vec<Entry>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(contents.lines().filter_map(Entry::from_json).collect()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        for entry in entries {
+            writeln!(file, "{}", entry.to_json())?;
+        }
+        Ok(())
+    }
+
+    /// The most recently recorded entry for a day/part.
+    pub fn latest(history: &[Entry], year: u16, day: u8, part: u8) -> Option<&Entry> {
+        history
+            .iter()
+            .rev()
+            .find(|entry| entry.year == year && entry.day == day && entry.part == part)
+    }
+
+    /// Turns the benchmarked parts of a run into history entries.
+    pub fn entries(year: u16, results: &[PartResult], commit: &str) -> Vec<Entry> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        results
+            .iter()
+            .filter(|result| result.is_solved())
+            .filter_map(|result| {
+                Some(Entry {
+                    commit: commit.into(),
+                    timestamp,
+                    year,
+                    day: result.day,
+                    part: result.part,
+                    stats: result.bench?,
+                })
+            })
+            .collect()
+    }
+
+    /// Compares each new entry against the most recent recorded entry for the same day/part.
+    pub fn find_regressions(
+        history: &[Entry],
+        current: &[Entry],
+        threshold: f64,
+    ) -> Vec<Regression> {
+        current
+            .iter()
+            .filter_map(|entry| {
+                let previous = latest(history, entry.year, entry.day, entry.part)?;
+
+                let before = previous.stats.median.as_nanos() as f64;
+                let after = entry.stats.median.as_nanos() as f64;
+                if before == 0_f64 {
+                    return None;
+                }
+
+                let slowdown = (after - before) / before * 100_f64;
+                (slowdown > threshold).then(|| Regression {
+                    day: entry.day,
+                    part: entry.part,
+                    previous: previous.clone(),
+                    current: entry.stats,
+                    slowdown,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn entry(commit: &str, day: u8, part: u8, median_us: u64) -> Entry {
+            let median = Duration::from_micros(median_us);
+            Entry {
+                commit: commit.into(),
+                timestamp: 1_670_000_000,
+                year: 2022,
+                day,
+                part,
+                stats: BenchStats {
+                    runs: 10,
+                    min: median,
+                    median,
+                    mean: median,
+                    stddev: Duration::ZERO,
+                },
+            }
+        }
+
+        #[test]
+        fn test_entry_roundtrip() {
+            let entry = entry("abc1234-dirty", 16, 2, 1500);
+            assert_eq!(Entry::from_json(&entry.to_json()), Some(entry));
+        }
+
+        #[test]
+        fn test_find_regressions_uses_latest_entry() {
+            let history = vec![
+                entry("a", 12, 2, 100),
+                entry("b", 12, 2, 200),
+                entry("b", 12, 1, 100),
+            ];
+            let current = vec![
+                entry("c", 12, 2, 210),
+                entry("c", 12, 1, 150),
+                entry("c", 13, 1, 500),
+            ];
+
+            let regressions = find_regressions(&history, &current, DEFAULT_THRESHOLD);
+
+            assert_eq!(regressions.len(), 1);
+            assert_eq!((regressions[0].day, regressions[0].part), (12, 1));
+            assert_eq!(regressions[0].previous.commit, "b");
+            assert!((regressions[0].slowdown - 50_f64).abs() < 1.0e-6);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].map(Duration::from_micros).to_vec();
+        let stats = BenchStats::from_samples(&samples).unwrap();
+
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        // sample standard deviation of [1, 2, 3, 4] is sqrt(5/3).
+        assert_eq!(stats.stddev, Duration::from_nanos(1291));
+
+        let single = BenchStats::from_samples(&[Duration::from_millis(5)]).unwrap();
+        assert_eq!(single.median, Duration::from_millis(5));
+        assert_eq!(single.stddev, Duration::ZERO);
+
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_bench_exact_runs() {
+        let calls = std::cell::Cell::new(0);
+        let options = BenchOptions {
+            warmup: 2,
+            runs: Some(5),
+            budget: Duration::ZERO,
+        };
+        let stats = bench(
+            |_| {
+                calls.set(calls.get() + 1);
+                Some(0)
+            },
+            "",
+            &options,
+        );
+
+        assert_eq!(stats.runs, 5);
+        assert_eq!(calls.get(), 7);
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::client::Client;
+use advent_of_code::session::{self, Session, Source, DEFAULT_PROFILE};
+use advent_of_code::{config, exit_code};
+use std::io::{self, BufRead};
+use std::process;
+
+const USAGE: &str = "usage:
+  cargo aoc-session show                      the session in use and the saved profiles
+  cargo aoc-session set [--profile <name>] [<token>]
+                                              save a token, read from stdin if not given
+  cargo aoc-session use <name>                make a profile the active one
+  cargo aoc-session validate [--profile <name>] [--year <year>]
+                                              check the token with the site";
+
+enum Command {
+    Show,
+    Set {
+        profile: String,
+        token: Option<String>,
+    },
+    Use {
+        profile: String,
+    },
+    Validate {
+        profile: Option<String>,
+        year: Option<u16>,
+    },
+}
+
+fn parse_args() -> Result<Command, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let command: String = args.free_from_str()?;
+
+    let command = match command.as_str() {
+        "show" => Command::Show,
+        "set" => Command::Set {
+            profile: args
+                .opt_value_from_str("--profile")?
+                .unwrap_or_else(|| DEFAULT_PROFILE.into()),
+            token: args.opt_free_from_str()?,
+        },
+        "use" => Command::Use {
+            profile: args.free_from_str()?,
+        },
+        "validate" => Command::Validate {
+            profile: args.opt_value_from_str("--profile")?,
+            year: args.opt_value_from_str(["-y", "--year"])?,
+        },
+        _ => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: format!("unknown command \"{}\"", command),
+            })
+        }
+    };
+
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected arguments {:?}", remaining),
+        });
+    }
+
+    Ok(command)
+}
+
+fn exit_with_error(message: impl std::fmt::Display, code: i32) -> ! {
+    eprintln!("{}", message);
+    process::exit(code);
+}
+
+fn main() {
+    let command = match parse_args() {
+        Ok(command) => command,
+        Err(e) => exit_with_error(
+            format!("Failed to process arguments: {}\n{}", e, USAGE),
+            exit_code::FAILURE,
+        ),
+    };
+
+    let result = match command {
+        Command::Show => show(),
+        Command::Set { profile, token } => set(&profile, token),
+        Command::Use { profile } => use_profile(&profile),
+        Command::Validate { profile, year } => validate(profile, year),
+    };
+
+    if let Err((message, code)) = result {
+        exit_with_error(message, code);
+    }
+}
+
+type CommandResult = Result<(), (String, i32)>;
+
+fn session_error(e: session::SessionError) -> (String, i32) {
+    (e.to_string(), exit_code::SESSION)
+}
+
+fn show() -> CommandResult {
+    match session::resolve() {
+        Ok(session) => println!("🎄 Using {} from {}.", session.masked(), session.source),
+        Err(e) => println!("{}", e),
+    }
+
+    let profiles = session::load_profiles().map_err(session_error)?;
+    if profiles.tokens.is_empty() {
+        return Ok(());
+    }
+
+    println!("---");
+    println!("Profiles in \"{}\":", session::profiles_path().display());
+    let selected = profiles.selected();
+    for (name, token) in &profiles.tokens {
+        let marker = if selected.as_deref() == Some(name) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{} {}: {}", marker, name, session::mask(token));
+    }
+
+    Ok(())
+}
+
+fn set(profile: &str, token: Option<String>) -> CommandResult {
+    if !session::is_valid_profile_name(profile) {
+        return Err((
+            format!(
+                "\"{}\" is not a valid profile name. Use letters, digits, `-` and `_`.",
+                profile
+            ),
+            exit_code::FAILURE,
+        ));
+    }
+
+    // Reading from stdin keeps the token out of the shell history.
+    let token = match token {
+        Some(token) => token,
+        None => {
+            eprintln!("Paste the value of the `session` cookie from adventofcode.com:");
+            let mut line = String::new();
+            io::stdin()
+                .lock()
+                .read_line(&mut line)
+                .map_err(|e| (e.to_string(), exit_code::IO))?;
+            line
+        }
+    };
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(("no token given.".into(), exit_code::FAILURE));
+    }
+
+    let mut profiles = session::load_profiles().map_err(session_error)?;
+    profiles.set(profile, token);
+    session::save_profiles(&profiles).map_err(session_error)?;
+
+    println!(
+        "🎄 Saved {} as profile \"{}\" in \"{}\".",
+        session::mask(token),
+        profile,
+        session::profiles_path().display()
+    );
+    Ok(())
+}
+
+fn use_profile(profile: &str) -> CommandResult {
+    let mut profiles = session::load_profiles().map_err(session_error)?;
+    if profiles.get(profile).is_none() {
+        return Err(session_error(session::SessionError::UnknownProfile {
+            name: profile.into(),
+            path: session::profiles_path(),
+        }));
+    }
+
+    profiles.active = Some(profile.into());
+    session::save_profiles(&profiles).map_err(session_error)?;
+    println!("🎄 Profile \"{}\" is now active.", profile);
+    Ok(())
+}
+
+fn validate(profile: Option<String>, year: Option<u16>) -> CommandResult {
+    let session = match profile {
+        Some(name) => {
+            let profiles = session::load_profiles().map_err(session_error)?;
+            match profiles.get(&name) {
+                Some(token) => Session {
+                    token: token.into(),
+                    source: Source::Profile {
+                        name,
+                        path: session::profiles_path(),
+                    },
+                },
+                None => {
+                    return Err(session_error(session::SessionError::UnknownProfile {
+                        name,
+                        path: session::profiles_path(),
+                    }))
+                }
+            }
+        }
+        None => session::resolve().map_err(session_error)?,
+    };
+    let year = config::resolve_year(year).map_err(|e| (e, exit_code::FAILURE))?;
+
+    let client = Client::with_session(&session);
+    match client.validate(year) {
+        Ok(true) => {
+            println!("🎄 {} from {} is valid.", session.masked(), session.source);
+            Ok(())
+        }
+        Ok(false) => Err((
+            format!(
+                "{} from {} was rejected. Log in again and save the new cookie with `cargo aoc-session set`.",
+                session.masked(),
+                session.source
+            ),
+            exit_code::SESSION,
+        )),
+        Err(e) => Err((
+            format!("could not validate the session: {}", e),
+            e.exit_code(),
+        )),
+    }
+}
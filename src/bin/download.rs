@@ -2,45 +2,144 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::client::{self, DownloadError};
+use advent_of_code::{config, exit_code, project_root, puzzle, unlock};
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::process::{self, Command};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "usage: cargo download <day> [--year <year>] [--refresh] [--wait [--scaffold]]
+  --refresh    download the puzzle description again, e.g. once part two is unlocked
+  --wait       wait until the puzzle unlocks, then download it
+  --scaffold   scaffold the day while waiting";
+
+/// Retries of a download that failed because the puzzle was not unlocked yet, about five
+/// minutes with [`unlock::backoff`].
+const MAX_RETRIES: u32 = 10;
 
 struct Args {
     day: u8,
     year: Option<u16>,
+    refresh: bool,
+    wait: bool,
+    scaffold: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
+    let refresh = args.contains("--refresh");
+    let wait = args.contains("--wait");
+    let scaffold = args.contains("--scaffold");
     Ok(Args {
         day: args.free_from_str()?,
         year: args.opt_value_from_str(["-y", "--year"])?,
+        refresh,
+        wait: wait || scaffold,
+        scaffold,
     })
 }
 
+fn exit_with_error(message: impl std::fmt::Display, code: i32) -> ! {
+    eprintln!("{}", message);
+    process::exit(code);
+}
+
+/// Runs `cargo scaffold` for the day. A day that was scaffolded before is left alone.
+fn scaffold(year: u16, day: u8) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let status = Command::new(cargo)
+        .current_dir(project_root())
+        .args(["scaffold", &day.to_string(), "--year", &year.to_string()])
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(_) => eprintln!("Scaffolding failed, downloading anyway."),
+        Err(e) => eprintln!("Could not run `cargo scaffold`: {}", e),
+    }
+}
+
+/// Sleeps until the puzzle unlocks, counting down on a terminal.
+fn wait_for_unlock(year: u16, day: u8) {
+    let unlock = Duration::from_secs(unlock::unlock_time(year, day));
+    let interactive = io::stdout().is_terminal();
+    let mut announced = false;
+
+    loop {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let Some(left) = unlock.checked_sub(now).filter(|left| !left.is_zero()) else {
+            break;
+        };
+
+        let countdown = unlock::format_countdown(left.as_secs_f64().ceil() as u64);
+        if interactive {
+            print!("\r⏳ Day {} unlocks in {} ", day, countdown);
+            io::stdout().flush().ok();
+        } else if !announced {
+            println!("⏳ Day {} unlocks in {}.", day, countdown);
+        }
+        announced = true;
+
+        // Wake up on the second so the countdown ticks evenly and the download starts on time.
+        let tick = match left.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(nanos.into()),
+        };
+        thread::sleep(tick.min(left));
+    }
+
+    if announced && interactive {
+        println!();
+    }
+}
+
+/// Downloads the puzzle, retrying with backoff while the site still reports it as locked.
+fn download(year: u16, day: u8, refresh: bool, retry: bool) -> Result<(), DownloadError> {
+    let mut attempt = 0;
+    loop {
+        match client::download(year, day, refresh) {
+            Err(e)
+                if retry && attempt < MAX_RETRIES && e.exit_code() == exit_code::NOT_UNLOCKED =>
+            {
+                let delay = unlock::backoff(attempt);
+                eprintln!("Not unlocked yet, retrying in {}s.", delay.as_secs());
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 fn main() {
     let args = match parse_args() {
         Ok(args) => args,
-        Err(e) => {
-            eprintln!("Failed to process arguments: {}", e);
-            process::exit(1);
-        }
+        Err(e) => exit_with_error(
+            format!("Failed to process arguments: {}\n{}", e, USAGE),
+            exit_code::FAILURE,
+        ),
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let year =
+        config::resolve_year(args.year).unwrap_or_else(|e| exit_with_error(e, exit_code::FAILURE));
+
+    if args.scaffold {
+        scaffold(year, args.day);
+    }
+    if args.wait {
+        wait_for_unlock(year, args.day);
     }
 
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
-            process::exit(1);
-        }
+    if let Err(e) = download(year, args.day, args.refresh, args.wait) {
+        exit_with_error(format!("Failed to download: {}", e), e.exit_code());
+    }
+
+    match puzzle::update_example(year, args.day, false) {
+        Ok(updated) => updated.print(),
+        Err(e) => exit_with_error(format!("Failed to store the example: {}", e), exit_code::IO),
     }
 }
@@ -2,8 +2,10 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::client::{self, Client, ClientError};
+use advent_of_code::config::{self, Backend};
+use advent_of_code::{aoc_cli, exit_code, render};
+use std::{fs, process};
 
 struct Args {
     day: u8,
@@ -18,29 +20,55 @@ fn parse_args() -> Result<Args, pico_args::Error> {
     })
 }
 
+fn exit_with_error(message: impl std::fmt::Display, code: i32) -> ! {
+    eprintln!("{}", message);
+    process::exit(code);
+}
+
 fn main() {
     let args = match parse_args() {
         Ok(args) => args,
-        Err(e) => {
-            eprintln!("Failed to process arguments: {}", e);
-            process::exit(1);
-        }
+        Err(e) => exit_with_error(
+            format!("Failed to process arguments: {}", e),
+            exit_code::FAILURE,
+        ),
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    let year =
+        config::resolve_year(args.year).unwrap_or_else(|e| exit_with_error(e, exit_code::FAILURE));
 
-    match aoc_cli::read(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
+    let path = client::puzzle_path(year, args.day);
+    if !path.exists() {
+        match config::get().backend {
+            Backend::Native => {
+                if let Err(e) = download_puzzle(args.day, year) {
+                    exit_with_error(format!("Failed to download puzzle: {}", e), e.exit_code());
+                }
+            }
+            // aoc-cli prints the puzzle itself.
+            Backend::AocCli => {
+                if let Err(e) = aoc_cli::read(args.day, year) {
+                    exit_with_error(format!("Failed to read puzzle: {}", e), e.exit_code());
+                }
+                return;
             }
         }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
-            process::exit(1);
-        }
     }
+
+    match fs::read_to_string(&path) {
+        Ok(puzzle) => print!(
+            "{}",
+            render::render(&puzzle, render::terminal_width(), config::get().use_color())
+        ),
+        Err(e) => exit_with_error(
+            format!("could not read \"{}\": {}", path.display(), e),
+            exit_code::IO,
+        ),
+    }
+}
+
+fn download_puzzle(day: u8, year: u16) -> Result<(), ClientError> {
+    let client = Client::from_env()?;
+    client::cached(&client::puzzle_path(year, day), || client.puzzle(year, day))?;
+    Ok(())
 }
@@ -2,83 +2,330 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::{client, config, examples, project_root, puzzle, year_dir};
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(_input: &str) -> Option<u32> {
+/// Parses the input first, then solves both parts from the parsed form.
+const DEFAULT_TEMPLATE: &str = r###"fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let _lines = parse(input);
     None
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<u64> {
+    let _lines = parse(input);
     None
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
-    }
+    // Checks `examples/{{day_padded}}/example.txt` against the answers in `example.toml`, which
+    // `cargo download` fills in from the puzzle description.
+    advent_of_code::example_tests!({{day}}, [example]);
+}
+"###;
+
+/// Reads the input as a `Grid` of characters, indexed `grid[(row, column)]`.
+const GRID_TEMPLATE: &str = r###"use advent_of_code::helpers::Grid;
+
+fn parse(input: &str) -> Grid<char> {
+    Grid::from_chars(input).expect("the input should be a rectangular map")
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let _grid = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let _grid = parse(input);
+    None
+}
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+fn main() {
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Checks `examples/{{day_padded}}/example.txt` against the answers in `example.toml`, which
+    // `cargo download` fills in from the puzzle description.
+    advent_of_code::example_tests!({{day}}, [example]);
+}
+"###;
+
+/// Takes the constants that differ between the examples and the real input as `Params`.
+const PARAMS_TEMPLATE: &str = r###"advent_of_code::params! {
+    pub struct Params {
+        /// Replace with the constants that differ between the examples and the real input.
+        /// Override them with `cargo solve {{day}} --size 10`, or in
+        /// `examples/{{day_padded}}/example.toml`.
+        size: u64 = 0,
     }
 }
+
+pub fn part_one(_input: &str, _params: &Params) -> Option<u64> {
+    None
+}
+
+pub fn part_two(_input: &str, _params: &Params) -> Option<u64> {
+    None
+}
+
+fn main() {
+    advent_of_code::solve!(1, part_one, params: Params);
+    advent_of_code::solve!(2, part_two, params: Params);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Checks `examples/{{day_padded}}/example.txt` against the answers in `example.toml`, which
+    // `cargo download` fills in from the puzzle description. Parameters go there too.
+    advent_of_code::example_tests!({{day}}, [example], params: Params);
+}
 "###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
+/// The built-in templates by name. `templates/<name>.rs` in the project root adds a template or
+/// replaces a built-in one. Templates may contain these placeholders:
+///
+/// - `{{day}}`: the day, e.g. `7`.
+/// - `{{day_padded}}`: the day padded to two digits, e.g. `07`.
+const TEMPLATES: [(&str, &str); 3] = [
+    ("default", DEFAULT_TEMPLATE),
+    ("grid", GRID_TEMPLATE),
+    ("params", PARAMS_TEMPLATE),
+];
+
+const USAGE: &str =
+    "cargo scaffold <day> [--year <year>] [--template <name>] [--overwrite] [--download]";
+
+struct Args {
+    day: u8,
+    year: Option<u16>,
+    template: String,
+    overwrite: bool,
+    download: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+    let params = args.contains("--params");
+    Ok(Args {
+        overwrite: args.contains("--overwrite"),
+        download: args.contains("--download"),
+        template: args
+            .opt_value_from_str(["-t", "--template"])?
+            .unwrap_or_else(|| if params { "params" } else { "default" }.into()),
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        day: args.free_from_str()?,
+    })
+}
+
+fn templates_dir() -> PathBuf {
+    project_root().join("templates")
+}
+
+/// The names of the built-in and user templates.
+fn template_names() -> Vec<String> {
+    let mut names: Vec<String> = TEMPLATES.iter().map(|(name, _)| name.to_string()).collect();
+    if let Ok(entries) = fs::read_dir(templates_dir()) {
+        names.extend(
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string())),
+        );
+    }
+    names.sort();
+    names.dedup();
+    names
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+fn load_template(name: &str) -> Result<String, String> {
+    let path = templates_dir().join(format!("{}.rs", name));
+    if path.exists() {
+        return fs::read_to_string(&path)
+            .map_err(|e| format!("could not read \"{}\": {}", path.display(), e));
+    }
+
+    match TEMPLATES.iter().find(|(builtin, _)| *builtin == name) {
+        Some((_, template)) => Ok(template.to_string()),
+        None => Err(format!(
+            "unknown template \"{}\". Available templates: {}.",
+            name,
+            template_names().join(", ")
+        )),
+    }
+}
+
+/// Fills in the placeholders listed at [`TEMPLATES`].
+fn render_template(template: &str, day: u8) -> String {
+    template
+        .replace("{{day}}", &day.to_string())
+        .replace("{{day_padded}}", &format!("{:02}", day))
+}
+
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
+    create_parent(path)?;
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    create_parent(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+}
+
+/// Creates an empty file unless one exists. Returns whether it was created.
+fn create_if_missing(path: &Path) -> Result<bool, std::io::Error> {
+    match safe_create_file(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn create_parent(path: &Path) -> Result<(), std::io::Error> {
+    match path.parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+/// Adds the day's `[[bin]]` target to `Cargo.toml`, named `<year>-<day>`.
+fn register_bin(year: u16, day_padded: &str) -> io::Result<bool> {
+    let path = project_root().join("Cargo.toml");
+    let contents = fs::read_to_string(&path)?;
+    let name = format!("name = \"{}-{}\"", year, day_padded);
+    if contents.contains(&name) {
+        return Ok(false);
+    }
+
+    let entry = format!(
+        "\n[[bin]]\n{}\npath = \"years/{}/bin/{}.rs\"\n",
+        name, year, day_padded
+    );
+    fs::write(&path, contents.trim_end().to_string() + "\n" + &entry)?;
+    Ok(true)
+}
+
+/// Adds the day to the `solutions!` list in `src/main.rs` so `cargo solve` and `cargo all` run it.
+fn register_solution(year: u16, day: u8, params: bool) -> io::Result<bool> {
+    let path = project_root().join("src").join("main.rs");
+    let contents = fs::read_to_string(&path)?;
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "no `solutions! { .. }` list found",
+        )
+    };
+    let start = contents.find("\nsolutions! {\n").ok_or_else(invalid)?;
+    let end = start + contents[start..].find("\n}\n").ok_or_else(invalid)?;
+
+    // Only the list counts, the macro's docs mention days as examples.
+    let key = format!("({}, {}) =>", year, day);
+    if contents[start..end].contains(&key) {
+        return Ok(false);
+    }
+
+    let line = format!(
+        "\n    {} y{}_day{:02} in \"../years/{}/bin/{:02}.rs\"{},",
+        key,
+        year,
+        day,
+        year,
+        day,
+        if params { " with Params" } else { "" }
+    );
+    fs::write(
+        &path,
+        format!("{}{}{}", &contents[..end], line, &contents[end..]),
+    )?;
+    Ok(true)
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}. usage: `{}`", e, USAGE);
             process::exit(1);
         }
     };
 
+    let year = match config::resolve_year(args.year) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let template = match load_template(&args.template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let params = template.contains("advent_of_code::params!");
+
+    let day = args.day;
     let day_padded = format!("{:02}", day);
 
-    let input_path = format!("src/inputs/{}.txt", day_padded);
-    let example_path = format!("src/examples/{}.txt", day_padded);
-    let module_path = format!("src/bin/{}.rs", day_padded);
+    let input_path = advent_of_code::input_path(year, "inputs", day);
+    let example_path = examples::examples_dir(year, day).join("example.txt");
+    let module_path = year_dir(year)
+        .join("bin")
+        .join(format!("{}.rs", day_padded));
 
-    let mut file = match safe_create_file(&module_path) {
+    let file = if args.overwrite {
+        create_file(&module_path)
+    } else {
+        safe_create_file(&module_path)
+    };
+    let mut file = match file {
         Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            eprintln!(
+                "Module file \"{}\" already exists. Pass `--overwrite` to replace it.",
+                module_path.display()
+            );
+            process::exit(1);
+        }
         Err(e) => {
             eprintln!("Failed to create module file: {}", e);
             process::exit(1);
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
+    match file.write_all(render_template(&template, day).as_bytes()) {
         Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {}", e);
@@ -86,20 +333,59 @@ fn main() {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    match register_bin(year, &day_padded) {
+        Ok(true) => println!("Added binary \"{}-{}\" to Cargo.toml", year, day_padded),
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to register binary in Cargo.toml: {}", e);
+            process::exit(1);
+        }
+    }
+
+    match register_solution(year, day, params) {
+        Ok(true) => println!("Registered day {} in src/main.rs", day),
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to register solution in src/main.rs: {}", e);
+            process::exit(1);
+        }
+    }
+
+    // The day is registered by now, so a failed download leaves a scaffold that works once
+    // `cargo download` succeeds.
+    let mut exit_code = 0;
+    if args.download {
+        if let Err(e) = client::download(year, day, false) {
+            eprintln!("Failed to download: {}", e);
+            eprintln!("Run `cargo download {} --year {}` to try again.", day, year);
+            exit_code = e.exit_code();
+        }
+    }
+
+    match create_if_missing(&input_path) {
+        Ok(true) => {
+            println!("Created empty input file \"{}\"", input_path.display());
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {}", e);
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    match puzzle::update_example(year, day, args.overwrite) {
+        Ok(updated) => updated.print(),
+        Err(e) => {
+            eprintln!("Failed to store the puzzle's example: {}", e);
+            process::exit(1);
+        }
+    }
+
+    match create_if_missing(&example_path) {
+        Ok(true) => {
+            println!("Created empty example file \"{}\"", example_path.display());
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create example file: {}", e);
             process::exit(1);
@@ -108,7 +394,8 @@ fn main() {
 
     println!("---");
     println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        day, year
     );
+    process::exit(exit_code);
 }
@@ -0,0 +1,707 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! A native client for the Advent of Code site, used by `cargo download` instead of aoc-cli.
+//!
+//! The session cookie comes from [`crate::session`]. `$AOC_BASE_URL` points the client at a
+//! different host, e.g. a local stand-in for testing. Downloaded files are cached: a file that
+//! already exists is never fetched again, unless a refresh of the puzzle description is asked
+//! for with `cargo download --refresh`.
+use crate::aoc_cli::{self, AocCliError};
+use crate::config::Backend;
+use crate::exit_code;
+use crate::session::{self, Session, SessionError};
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum ClientError {
+    Session(SessionError),
+    Status {
+        url: String,
+        status: u16,
+    },
+    Transport {
+        url: String,
+        source: Box<ureq::Transport>,
+    },
+    Body {
+        url: String,
+        source: io::Error,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Session(e) => e.fmt(f),
+            ClientError::Status { url, status } => {
+                write!(f, "request to \"{}\" failed with status {}.", url, status)
+            }
+            ClientError::Transport { url, source } => {
+                write!(f, "request to \"{}\" failed: {}", url, source)
+            }
+            ClientError::Body { url, source } => {
+                write!(
+                    f,
+                    "could not read the response from \"{}\": {}",
+                    url, source
+                )
+            }
+            ClientError::Io { path, source } => {
+                write!(f, "could not write \"{}\": {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Session(e) => e.source(),
+            ClientError::Transport { source, .. } => Some(source.as_ref()),
+            ClientError::Body { source, .. } | ClientError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl ClientError {
+    /// The exit code for this error (see [`crate::exit_code`]). The site answers 400 to input
+    /// requests without a valid session and 404 to puzzles that are not unlocked yet.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ClientError::Session(_) | ClientError::Status { status: 400, .. } => exit_code::SESSION,
+            ClientError::Status { status: 404, .. } => exit_code::NOT_UNLOCKED,
+            ClientError::Status { .. } => exit_code::FAILURE,
+            ClientError::Transport { .. } | ClientError::Body { .. } => exit_code::NETWORK,
+            ClientError::Io { .. } => exit_code::IO,
+        }
+    }
+}
+
+/// A failure of [`download`] with either backend.
+#[derive(Debug)]
+pub enum DownloadError {
+    AocCli(AocCliError),
+    Client(ClientError),
+}
+
+impl Display for DownloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DownloadError::AocCli(e) => e.fmt(f),
+            DownloadError::Client(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for DownloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DownloadError::AocCli(e) => e.source(),
+            DownloadError::Client(e) => e.source(),
+        }
+    }
+}
+
+impl From<AocCliError> for DownloadError {
+    fn from(e: AocCliError) -> Self {
+        DownloadError::AocCli(e)
+    }
+}
+
+impl From<ClientError> for DownloadError {
+    fn from(e: ClientError) -> Self {
+        DownloadError::Client(e)
+    }
+}
+
+impl DownloadError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DownloadError::AocCli(e) => e.exit_code(),
+            DownloadError::Client(e) => e.exit_code(),
+        }
+    }
+}
+
+/// The user agent of requests to the site: the crate and version, followed by the `cli.contact`
+/// from `aoc.toml` if it is set.
+fn user_agent(contact: Option<&str>) -> String {
+    let agent = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+    match contact {
+        Some(contact) => format!("{} ({})", agent, contact),
+        None => agent.to_string(),
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Client {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into().trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent(crate::config::get().contact.as_deref()))
+                .build(),
+        }
+    }
+
+    /// A client for `$AOC_BASE_URL` (or the real site) with the session cookie from
+    /// [`session::resolve`].
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = session::resolve().map_err(ClientError::Session)?;
+        Ok(Client::with_session(&session))
+    }
+
+    /// A client for `$AOC_BASE_URL` (or the real site) with the given session cookie.
+    pub fn with_session(session: &Session) -> Self {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Client::new(base_url, &session.token)
+    }
+
+    /// Whether the site accepts the session cookie. It answers 400 to input requests from
+    /// visitors who are not logged in, so this asks for the input of day 1 of `year`.
+    pub fn validate(&self, year: u16) -> Result<bool, ClientError> {
+        match self.input(year, 1) {
+            Ok(_) => Ok(true),
+            Err(ClientError::Status { status: 400, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// The puzzle description, converted to markdown.
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        self.get(&format!("/{}/day/{}", year, day))
+            .map(|html| puzzle_markdown(&html))
+    }
+
+    /// Submits an answer and returns the site's reply, converted to markdown.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, ClientError> {
+        let url = self.url(&format!("/{}/day/{}/answer", year, day));
+        let level = part.to_string();
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", &level), ("answer", answer)]);
+        read_response(url, response).map(|html| puzzle_markdown(&html))
+    }
+
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        let url = self.url(path);
+        let response = self.request("GET", &url).call();
+        read_response(url, response)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+}
+
+fn read_response(
+    url: String,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, ClientError> {
+    let response = response.map_err(|e| match e {
+        ureq::Error::Status(status, _) => ClientError::Status {
+            url: url.clone(),
+            status,
+        },
+        ureq::Error::Transport(source) => ClientError::Transport {
+            url: url.clone(),
+            source: Box::new(source),
+        },
+    })?;
+
+    response
+        .into_string()
+        .map_err(|source| ClientError::Body { url, source })
+}
+
+pub fn puzzle_path(year: u16, day: u8) -> PathBuf {
+    crate::config::get()
+        .dir("puzzles", year)
+        .join(format!("{:02}.md", day))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+    /// The file existed and was fetched again with [`refetch`].
+    Refreshed,
+}
+
+/// Writes the result of `fetch` to `path` unless the file already exists.
+pub fn cached(
+    path: &Path,
+    fetch: impl FnOnce() -> Result<String, ClientError>,
+) -> Result<Fetched, ClientError> {
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    write_fetched(path, &fetch()?)?;
+    Ok(Fetched::Downloaded)
+}
+
+/// Writes the result of `fetch` to `path`, replacing the file if it exists. The existing copy is
+/// only replaced once `fetch` succeeds.
+pub fn refetch(
+    path: &Path,
+    fetch: impl FnOnce() -> Result<String, ClientError>,
+) -> Result<Fetched, ClientError> {
+    let contents = fetch()?;
+    let existed = path.exists();
+
+    write_fetched(path, &contents)?;
+    Ok(if existed {
+        Fetched::Refreshed
+    } else {
+        Fetched::Downloaded
+    })
+}
+
+fn write_fetched(path: &Path, contents: &str) -> Result<(), ClientError> {
+    let io_error = |source| ClientError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    // an interrupted write must not be mistaken for a cached copy.
+    let tmp = path.with_extension("part");
+    fs::write(&tmp, contents).map_err(io_error)?;
+    fs::rename(&tmp, path).map_err(io_error)
+}
+
+type Fetch = fn(&Client, u16, u8) -> Result<String, ClientError>;
+
+/// Downloads the day's input and puzzle description with the backend set in the config. The
+/// native backend keeps files that were downloaded before. With `refresh`, it fetches the puzzle
+/// description again, e.g. to pick up part two once part one is solved; the input never changes
+/// and stays cached.
+pub fn download(year: u16, day: u8, refresh: bool) -> Result<(), DownloadError> {
+    if crate::config::get().backend == Backend::AocCli {
+        aoc_cli::download(day, year)?;
+        return Ok(());
+    }
+
+    let client = Client::from_env()?;
+    let files: [(&str, PathBuf, Fetch, bool); 2] = [
+        (
+            "input",
+            crate::input_path(year, "inputs", day),
+            Client::input,
+            false,
+        ),
+        ("puzzle", puzzle_path(year, day), Client::puzzle, refresh),
+    ];
+
+    for (name, path, fetch, refresh) in files {
+        let fetched = if refresh {
+            refetch(&path, || fetch(&client, year, day))?
+        } else {
+            cached(&path, || fetch(&client, year, day))?
+        };
+        match fetched {
+            Fetched::Downloaded => {
+                println!("🎄 Successfully wrote {} to \"{}\".", name, path.display())
+            }
+            Fetched::Refreshed => {
+                println!("🎄 Updated {} at \"{}\".", name, path.display())
+            }
+            Fetched::Cached => {
+                println!("🎄 Using cached {} at \"{}\".", name, path.display())
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts a puzzle page to markdown in the style aoc-cli writes: the `<article>` elements
+/// with the puzzle text, followed by the "Your puzzle answer was" line of each solved part.
+pub fn puzzle_markdown(html: &str) -> String {
+    const ANSWER: &str = "<p>Your puzzle answer was";
+
+    let mut out = String::new();
+    let mut rest = html;
+    loop {
+        let article = rest.find("<article");
+        let answer = rest.find(ANSWER);
+        let (start, end_tag) = match (article, answer) {
+            (Some(a), Some(b)) if b < a => (b, "</p>"),
+            (Some(a), _) => (a, "</article>"),
+            (None, Some(b)) => (b, "</p>"),
+            (None, None) => break,
+        };
+
+        let section = &rest[start..];
+        let end = section
+            .find(end_tag)
+            .map_or(section.len(), |i| i + end_tag.len());
+        render_html(&section[..end], &mut out);
+        rest = &section[end..];
+    }
+
+    format!("{}\n", out.trim_end())
+}
+
+fn render_html(html: &str, out: &mut String) {
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(rest, in_pre, out);
+            break;
+        };
+        push_text(&rest[..tag_start], in_pre, out);
+
+        let tag_end = rest[tag_start..]
+            .find('>')
+            .map_or(rest.len(), |i| tag_start + i + 1);
+        let tag = &rest[tag_start..tag_end];
+        rest = &rest[tag_end..];
+
+        let closing = tag.starts_with("</");
+        let name: String = tag
+            .trim_start_matches(['<', '/'])
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect();
+
+        match (name.as_str(), closing) {
+            ("h2", false) => out.push('\\'),
+            ("h2", true) => out.push_str("\n----------\n\n"),
+            ("p", true) => out.push_str("\n\n"),
+            ("ul", true) => out.push('\n'),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("* "),
+            ("li", true) => out.push('\n'),
+            ("br", _) => out.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({})", href));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_text(text: &str, in_pre: bool, out: &mut String) {
+    // whitespace between block elements is layout only.
+    if !in_pre && text.trim().is_empty() && (out.is_empty() || out.ends_with('\n')) {
+        return;
+    }
+    out.push_str(&decode_entities(text));
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                entity => {
+                    let code = entity.strip_prefix('#')?;
+                    let code = match code.strip_prefix('x') {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => code.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end + 1))
+        });
+
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    const PUZZLE_HTML: &str = "<html><body><main>\
+        <article class=\"day-desc\"><h2>--- Day 1: Calorie Counting ---</h2>\n\
+        <p>The Elves take turns writing down the number of <em>Calories</em> &amp; more:</p>\n\
+        <pre><code>1000\n2000\n\n&lt;3000&gt;\n</code></pre>\n\
+        <ul><li>The first Elf</li><li>The <a href=\"/2022/day/1/input\">second</a> Elf</li></ul>\n\
+        <p>In total, that's <code><em>24000</em></code> Calories.</p>\n\
+        </article>\n<p>Your puzzle answer was <code>1</code>.</p></main></body></html>";
+
+    const ANSWER_HTML: &str = "<main><article><p>That's the right answer! \
+        <a href=\"/2022/day/1#part2\">[Continue to Part Two]</a></p></article></main>";
+
+    /// Serves `/2022/day/1`, `/2022/day/1/input` and `/2022/day/1/answer` to the session
+    /// `abc`, and records every request's path, cookie header and form body.
+    fn mock_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let log = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let path = line.split(' ').nth(1).unwrap().to_string();
+
+                let (mut cookie, mut length) = (String::new(), 0);
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    let Some((name, value)) = line.trim_end().split_once(": ") else {
+                        break;
+                    };
+                    match name.to_lowercase().as_str() {
+                        "cookie" => cookie = value.to_string(),
+                        "content-length" => length = value.parse().unwrap(),
+                        _ => {}
+                    }
+                }
+                let mut form = vec![0; length];
+                reader.read_exact(&mut form).unwrap();
+                let form = String::from_utf8(form).unwrap();
+                log.lock()
+                    .unwrap()
+                    .push(format!("{} {} {}", path, cookie, form).trim().into());
+
+                let (status, body) = match path.as_str() {
+                    _ if cookie != "session=abc" => ("400 Bad Request", "Please log in."),
+                    "/2022/day/1" => ("200 OK", PUZZLE_HTML),
+                    "/2022/day/1/input" => ("200 OK", "1000\n2000\n"),
+                    "/2022/day/1/answer" => ("200 OK", ANSWER_HTML),
+                    _ => ("404 Not Found", "not found"),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (base_url, requests) = mock_server();
+        let client = Client::new(base_url, "abc\n");
+
+        assert_eq!(client.input(2022, 1).unwrap(), "1000\n2000\n");
+        assert!(client.puzzle(2022, 1).unwrap().starts_with("\\--- Day 1"));
+        let locked = client.input(2022, 2).unwrap_err();
+        assert!(matches!(locked, ClientError::Status { status: 404, .. }));
+        assert_eq!(locked.exit_code(), exit_code::NOT_UNLOCKED);
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                "/2022/day/1/input session=abc",
+                "/2022/day/1 session=abc",
+                "/2022/day/2/input session=abc"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_user_agent() {
+        let agent = user_agent(None);
+        assert!(agent.starts_with("advent_of_code/"));
+        assert_eq!(
+            user_agent(Some("you@example.com")),
+            format!("{} (you@example.com)", agent)
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let (base_url, _) = mock_server();
+        assert!(Client::new(&base_url, "abc").validate(2022).unwrap());
+        assert!(!Client::new(&base_url, "expired").validate(2022).unwrap());
+
+        let error = Client::new(&base_url, "abc").validate(2021).unwrap_err();
+        assert_eq!(error.exit_code(), exit_code::NOT_UNLOCKED);
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, requests) = mock_server();
+        let client = Client::new(base_url, "abc");
+
+        assert_eq!(
+            client.submit(2022, 1, 2, "45000").unwrap(),
+            "That's the right answer! [[Continue to Part Two]](/2022/day/1#part2)\n"
+        );
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["/2022/day/1/answer session=abc level=2&answer=45000"]
+        );
+    }
+
+    #[test]
+    fn test_cached_is_never_refetched() {
+        let (base_url, requests) = mock_server();
+        let client = Client::new(base_url, "abc");
+        let path = temp_dir("cached").join("inputs").join("01.txt");
+
+        assert_eq!(
+            cached(&path, || client.input(2022, 1)).unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(
+            cached(&path, || client.input(2022, 1)).unwrap(),
+            Fetched::Cached
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let missing = path.with_file_name("02.txt");
+        assert!(cached(&missing, || client.input(2022, 2)).is_err());
+        assert!(!missing.exists());
+    }
+
+    #[test]
+    fn test_refetch() {
+        let (base_url, requests) = mock_server();
+        let client = Client::new(base_url, "abc");
+        let path = temp_dir("refetch").join("puzzles").join("01.md");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        fs::write(&path, "\\--- Day 1 ---\n").unwrap();
+        assert_eq!(
+            cached(&path, || client.puzzle(2022, 1)).unwrap(),
+            Fetched::Cached
+        );
+        assert!(requests.lock().unwrap().is_empty());
+
+        assert_eq!(
+            refetch(&path, || client.puzzle(2022, 1)).unwrap(),
+            Fetched::Refreshed
+        );
+        assert!(fs::read_to_string(&path).unwrap().contains("24000"));
+
+        // A failed fetch keeps the cached copy.
+        let locked = path.with_file_name("02.md");
+        fs::write(&locked, "\\--- Day 2 ---\n").unwrap();
+        assert!(refetch(&locked, || client.puzzle(2022, 2)).is_err());
+        assert_eq!(fs::read_to_string(&locked).unwrap(), "\\--- Day 2 ---\n");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_puzzle_markdown() {
+        assert_eq!(
+            puzzle_markdown(PUZZLE_HTML),
+            "\\--- Day 1: Calorie Counting ---\n----------\n\n\
+             The Elves take turns writing down the number of *Calories* & more:\n\n\
+             ```\n1000\n2000\n\n<3000>\n```\n\n\
+             * The first Elf\n* The [second](/2022/day/1/input) Elf\n\n\
+             In total, that's `*24000*` Calories.\n\n\
+             Your puzzle answer was `1`.\n"
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;&#62;&#x41; &amp b"), "a <>A &amp b");
+    }
+}
@@ -0,0 +1,278 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Project settings, read once from `aoc.toml` in the project root. Every key is optional:
+//!
+//! ```toml
+//! # The year commands use when they are not given `--year`.
+//! year = 2022
+//! # The number of puzzles in a year.
+//! days = 25
+//!
+//! [dirs]
+//! # Where each kind of file lives. `{year}` is replaced with the year, relative paths are
+//! # resolved from the project root.
+//! inputs = "years/{year}/inputs"
+//! examples = "years/{year}/examples"
+//! puzzles = "years/{year}/puzzles"
+//! answers = "years/{year}/answers"
+//! guesses = "years/{year}/guesses"
+//!
+//! [cli]
+//! # `native` talks to the site directly, `aoc-cli` runs the aoc-cli executable.
+//! backend = "native"
+//! command = "aoc"
+//! # Print the aoc-cli command line before running it.
+//! verbose = false
+//! # Added to the user agent of requests to the site, so its maintainers can reach you.
+//! contact = "you@example.com"
+//!
+//! [session]
+//! # The session cookie is read from this environment variable, then the selected profile in the
+//! # profiles file (see `cargo aoc-session`), then this file.
+//! env = "AOC_SESSION"
+//! profiles = "~/.adventofcode.profiles.toml"
+//! # The profile to use instead of the active one. `$AOC_PROFILE` takes precedence.
+//! profile = "alice"
+//! file = "~/.adventofcode.session"
+//!
+//! [output]
+//! # `human` or `json`. `--json` switches a single run to JSON.
+//! format = "human"
+//! # `auto` colors output when writing to a terminal and `$NO_COLOR` is not set.
+//! color = "auto"
+//! ```
+use crate::runner::OutputFormat;
+use crate::toml;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::process;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Native,
+    AocCli,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub year: Option<u16>,
+    pub days: u8,
+    /// Directory templates by folder name, e.g. `"inputs" => "years/{year}/inputs"`.
+    pub dirs: HashMap<String, String>,
+    pub backend: Backend,
+    pub aoc_cli_command: String,
+    pub verbose: bool,
+    pub contact: Option<String>,
+    pub session_env: String,
+    pub session_profiles: String,
+    pub session_profile: Option<String>,
+    pub session_file: Option<String>,
+    pub format: OutputFormat,
+    pub color: Color,
+}
+
+pub const FOLDERS: [&str; 5] = ["inputs", "examples", "puzzles", "answers", "guesses"];
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            days: 25,
+            dirs: HashMap::new(),
+            backend: Backend::Native,
+            aoc_cli_command: "aoc".into(),
+            verbose: false,
+            contact: None,
+            session_env: "AOC_SESSION".into(),
+            session_profiles: "~/.adventofcode.profiles.toml".into(),
+            session_profile: None,
+            session_file: Some("~/.adventofcode.session".into()),
+            format: OutputFormat::Human,
+            color: Color::Auto,
+        }
+    }
+}
+
+impl Config {
+    /// Applies the settings in `contents` to the defaults. Unknown keys are an error.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut config = Config::default();
+
+        for (key, value) in toml::parse(contents)? {
+            let invalid = || format!("invalid value \"{}\" for `{}`.", value, key);
+
+            match key.as_str() {
+                "year" => config.year = Some(value.parse().map_err(|_| invalid())?),
+                "days" => {
+                    config.days = value
+                        .parse()
+                        .ok()
+                        .filter(|days| (1..=25).contains(days))
+                        .ok_or_else(invalid)?
+                }
+                "cli.backend" => {
+                    config.backend = match value.as_str() {
+                        "native" => Backend::Native,
+                        "aoc-cli" => Backend::AocCli,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "cli.command" => config.aoc_cli_command = value,
+                "cli.verbose" => config.verbose = value.parse().map_err(|_| invalid())?,
+                "cli.contact" => config.contact = Some(value).filter(|v| !v.is_empty()),
+                "session.env" => config.session_env = value,
+                "session.profiles" => config.session_profiles = value,
+                "session.profile" => config.session_profile = Some(value).filter(|v| !v.is_empty()),
+                "session.file" => config.session_file = Some(value).filter(|v| !v.is_empty()),
+                "output.format" => {
+                    config.format = match value.as_str() {
+                        "human" => OutputFormat::Human,
+                        "json" => OutputFormat::Json,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "output.color" => {
+                    config.color = match value.as_str() {
+                        "auto" => Color::Auto,
+                        "always" => Color::Always,
+                        "never" => Color::Never,
+                        _ => return Err(invalid()),
+                    }
+                }
+                _ => match key.strip_prefix("dirs.") {
+                    Some(folder) if FOLDERS.contains(&folder) => {
+                        config.dirs.insert(folder.into(), value);
+                    }
+                    _ => return Err(format!("unknown key `{}`.", key)),
+                },
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// The directory for a kind of file in a year, `years/<year>/<folder>` unless configured.
+    pub fn dir(&self, folder: &str, year: u16) -> PathBuf {
+        let template = self
+            .dirs
+            .get(folder)
+            .cloned()
+            .unwrap_or_else(|| format!("years/{{year}}/{}", folder));
+        resolve_path(&template.replace("{year}", &year.to_string()))
+    }
+
+    pub fn use_color(&self) -> bool {
+        match self.color {
+            Color::Always => true,
+            Color::Never => false,
+            Color::Auto => io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+        }
+    }
+}
+
+/// Resolves a configured path: `~/` is the home directory, relative paths start at the project
+/// root.
+pub fn resolve_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => crate::project_root().join(path),
+    }
+}
+
+pub fn config_path() -> PathBuf {
+    crate::project_root().join("aoc.toml")
+}
+
+/// Reads `aoc.toml`. A missing file gives the defaults.
+pub fn load() -> Result<Config, String> {
+    let path = config_path();
+    match fs::read_to_string(&path) {
+        Ok(contents) => Config::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(format!("could not read \"{}\": {}", path.display(), e)),
+    }
+}
+
+/// The project's config, loaded once per process. Prints the error and exits if `aoc.toml` is
+/// invalid.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| {
+        load().unwrap_or_else(|e| {
+            eprintln!("Failed to read config: {}", e);
+            process::exit(1);
+        })
+    })
+}
+
+/// The `year` set in `aoc.toml`.
+pub fn default_year() -> Result<u16, String> {
+    get()
+        .year
+        .ok_or(format!("{}: no `year` set.", config_path().display()))
+}
+
+/// `year` if given, otherwise the default year.
+pub fn resolve_year(year: Option<u16>) -> Result<u16, String> {
+    match year {
+        Some(year) => Ok(year),
+        None => default_year(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            "year = 2021\ndays = 12\n[cli]\nbackend = \"aoc-cli\"\n\
+             [output]\nformat = \"json\"\ncolor = \"never\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2021));
+        assert_eq!(config.days, 12);
+        assert_eq!(config.backend, Backend::AocCli);
+        assert_eq!(config.format, OutputFormat::Json);
+        assert!(!config.use_color());
+        assert_eq!(config.session_env, "AOC_SESSION");
+        assert_eq!(config.contact, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Config::parse("days = 26").is_err());
+        assert!(Config::parse("[cli]\nbackend = \"curl\"").is_err());
+        assert!(Config::parse("[dirs]\nsolutions = \"x\"").is_err());
+        assert!(Config::parse("colour = \"auto\"").is_err());
+    }
+
+    #[test]
+    fn test_dir() {
+        let config = Config::parse("[dirs]\ninputs = \"data/{year}\"\n").unwrap();
+        assert_eq!(
+            config.dir("inputs", 2022),
+            crate::project_root().join("data/2022")
+        );
+        assert_eq!(
+            config.dir("examples", 2022),
+            crate::project_root().join("years/2022/examples")
+        );
+    }
+}
@@ -0,0 +1,216 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Worked examples with expected answers, stored in `years/<year>/examples/NN/`.
+//!
+//! Each example is a `<name>.txt` input with an optional `<name>.toml` next to it:
+//!
+//! ```toml
+//! part1 = 26
+//! part2 = 56000011
+//! # anything else is a parameter for the solution
+//! row = 10
+//! ```
+//!
+//! A day with a single example can keep using `examples/NN.txt` and `read_file`.
+use crate::params::Params;
+use crate::toml;
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Generates one `#[test]` per named example, checking both parts against the example's
+/// expected answers. Parts without an expected answer are skipped.
+///
+/// ```ignore
+/// advent_of_code::example_tests!(9, [small, larger]);
+/// // Parameterised solutions get the example's parameters applied to their defaults:
+/// advent_of_code::example_tests!(15, [example], params: Params);
+/// // Anything else can be called with closures that receive the example:
+/// advent_of_code::example_tests!(17, [example],
+///     |input, example| part_one(input, example.param("rocks")?),
+///     |input, _| part_two(input));
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($day:expr, [$($name:ident),+ $(,)?]) => {
+        advent_of_code::example_tests!(
+            $day,
+            [$($name),+],
+            |input, _| part_one(input),
+            |input, _| part_two(input)
+        );
+    };
+    ($day:expr, [$($name:ident),+ $(,)?], params: $params:ty) => {
+        advent_of_code::example_tests!(
+            $day,
+            [$($name),+],
+            |input, example| part_one(input, &example.parse_params::<$params>().unwrap()),
+            |input, example| part_two(input, &example.parse_params::<$params>().unwrap())
+        );
+    };
+    ($day:expr, [$($name:ident),+ $(,)?], $one:expr, $two:expr) => {
+        mod examples {
+            use super::*;
+
+            $(
+                #[test]
+                fn $name() {
+                    let (year, _) = advent_of_code::runner::year_and_day_from_path(file!());
+                    let example =
+                        advent_of_code::examples::Example::load(year, $day, stringify!($name))
+                            .unwrap_or_else(|e| panic!("{}", e));
+                    example.check(1, $one);
+                    example.check(2, $two);
+                }
+            )+
+        }
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub year: u16,
+    pub day: u8,
+    pub name: String,
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+    /// Every other key in the example's `.toml`, in file order.
+    pub params: Vec<(String, String)>,
+}
+
+pub fn examples_dir(year: u16, day: u8) -> PathBuf {
+    crate::config::get()
+        .dir("examples", year)
+        .join(format!("{:02}", day))
+}
+
+impl Example {
+    pub fn load(year: u16, day: u8, name: &str) -> Result<Self, String> {
+        let dir = examples_dir(year, day);
+        let input_path = dir.join(format!("{}.txt", name));
+        let meta_path = dir.join(format!("{}.toml", name));
+
+        let input = fs::read_to_string(&input_path)
+            .map_err(|e| format!("could not read \"{}\": {}", input_path.display(), e))?;
+
+        let mut example = Example {
+            year,
+            day,
+            name: name.into(),
+            input,
+            part_one: None,
+            part_two: None,
+            params: vec![],
+        };
+
+        if meta_path.exists() {
+            let contents = fs::read_to_string(&meta_path)
+                .map_err(|e| format!("could not read \"{}\": {}", meta_path.display(), e))?;
+            let pairs =
+                toml::parse(&contents).map_err(|e| format!("{}: {}", meta_path.display(), e))?;
+
+            for (key, value) in pairs {
+                match key.as_str() {
+                    "part1" => example.part_one = Some(value),
+                    "part2" => example.part_two = Some(value),
+                    _ => example.params.push((key, value)),
+                }
+            }
+        }
+
+        Ok(example)
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Parses a parameter. Returns `None` if it is missing or does not parse as `T`.
+    pub fn param<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, value)| value.parse().ok())
+    }
+
+    /// The [`Params`] defaults with this example's parameters applied.
+    pub fn parse_params<P: Params>(&self) -> Result<P, String> {
+        P::from_pairs(self.params.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .map_err(|e| format!("example \"{}\": {}", self.name, e))
+    }
+
+    /// Asserts that `func` produces the expected answer for `part`, if there is one.
+    pub fn check<T: Display>(&self, part: u8, func: impl Fn(&str, &Example) -> Option<T>) {
+        let Some(expected) = self.expected(part) else {
+            return;
+        };
+
+        let answer = func(&self.input, self).map(|answer| answer.to_string());
+        assert_eq!(
+            answer.as_deref().map(str::trim),
+            Some(expected.trim()),
+            "day {:02}, example \"{}\", part {}",
+            self.day,
+            self.name,
+            part
+        );
+    }
+}
+
+/// Loads every example of a day, sorted by name. A day without an examples directory has none.
+pub fn all(year: u16, day: u8) -> Result<Vec<Example>, String> {
+    let dir = examples_dir(year, day);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(vec![]),
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+
+    names
+        .iter()
+        .map(|name| Example::load(year, day, name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_with_params() {
+        let example = Example::load(2022, 15, "example").unwrap();
+        assert_eq!(example.expected(1), Some("26"));
+        assert_eq!(example.expected(2), Some("56000011"));
+        assert_eq!(example.param::<i32>("row"), Some(10));
+        assert_eq!(example.param::<i32>("missing"), None);
+    }
+
+    #[test]
+    fn test_all() {
+        let names: Vec<String> = all(2022, 9).unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["larger", "small"]);
+        assert_eq!(all(2022, 25).unwrap(), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "example \"example\", part 1")]
+    fn test_check_mismatch() {
+        let example = Example::load(2022, 15, "example").unwrap();
+        example.check(1, |_, _| Some(0));
+    }
+}
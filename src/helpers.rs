@@ -2,3 +2,10 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+pub mod cycle;
+pub mod grid;
+pub mod interval;
+pub mod parse;
+pub mod search;
+
+pub use grid::{Grid, Pos};
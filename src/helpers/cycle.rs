@@ -0,0 +1,219 @@
+//! Finding where a simulation starts to repeat itself, to skip ahead to step counts far too large
+//! to simulate.
+//!
+//! A simulation is in a cycle once a state comes back: from then on, every `period` steps bring
+//! the same state again. What usually matters is a number that grows along with it, such as the
+//! height of a tower. [`Cycle::extrapolate`] computes it for any step from the values seen
+//! during the first pass through the cycle.
+//!
+//! [`CycleDetector`] follows a simulation as it runs and remembers a fingerprint of every state,
+//! while [`brent`] needs no memory but has to clone the state and replay steps.
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step of the first pass through the cycle.
+    pub start: usize,
+    /// The number of steps after which the states repeat.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The step before the end of the first pass through the cycle that is in the same state
+    /// as `step`.
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start + self.period {
+            step
+        } else {
+            self.start + (step - self.start) % self.period
+        }
+    }
+
+    /// How much `metric` changes with every pass through the cycle.
+    pub fn delta<T: Sub<Output = T>>(&self, mut metric: impl FnMut(usize) -> T) -> T {
+        metric(self.start + self.period) - metric(self.start)
+    }
+
+    /// The value of `metric` at `step`. `metric` is only called for steps up to
+    /// `start + period`, so the values of the first pass are enough. For unsigned types the
+    /// metric may not decrease over a pass.
+    pub fn extrapolate<T>(&self, step: usize, mut metric: impl FnMut(usize) -> T) -> T
+    where
+        T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + TryFrom<usize>,
+    {
+        let equivalent = self.equivalent_step(step);
+        if equivalent == step {
+            return metric(step);
+        }
+
+        let passes = (step - self.start) / self.period;
+        let Ok(passes) = T::try_from(passes) else {
+            panic!(
+                "{} passes through the cycle don't fit the metric type",
+                passes
+            );
+        };
+        metric(equivalent) + passes * self.delta(metric)
+    }
+}
+
+/// Remembers the step at which each state fingerprint was first seen. Observe the fingerprint
+/// of the initial state and then of the state after each step, until one comes back.
+///
+/// A fingerprint has to identify the whole state the simulation continues from, but can leave
+/// out what only adds up, like a tower's height.
+#[derive(Debug, Clone)]
+pub struct CycleDetector<K> {
+    seen: HashMap<K, usize>,
+    step: usize,
+}
+
+impl<K: Eq + Hash> CycleDetector<K> {
+    pub fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+            step: 0,
+        }
+    }
+
+    /// Records the fingerprint of the next step's state, starting from step 0. Returns the cycle
+    /// once a fingerprint is seen for the second time.
+    pub fn observe(&mut self, fingerprint: K) -> Option<Cycle> {
+        let step = self.step;
+        self.step += 1;
+        match self.seen.get(&fingerprint) {
+            Some(&start) => Some(Cycle {
+                start,
+                period: step - start,
+            }),
+            None => {
+                self.seen.insert(fingerprint, step);
+                None
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash> Default for CycleDetector<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Brent's algorithm: finds the cycle of the states reached by repeatedly applying `step` to
+/// `initial`, comparing them by `key`. Keeps at most two states around, at the cost of running
+/// about three times as many steps as there are until the cycle has been passed through once.
+///
+/// Doesn't return if the states never repeat.
+pub fn brent<S: Clone, K: PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    // Find the period: the tortoise waits at powers of two for the hare to come around.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = key(&initial);
+    let mut hare = step(&initial);
+    while key(&hare) != tortoise {
+        if power == period {
+            tortoise = key(&hare);
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // Find the start: walk two states `period` steps apart until they meet.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 5, 6, 3, 4, 5, 6, 3, ...
+    fn next(state: &u32) -> u32 {
+        if *state == 6 {
+            3
+        } else {
+            state + 1
+        }
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(
+            brent(0, next, |state| *state),
+            Cycle {
+                start: 3,
+                period: 4
+            }
+        );
+        assert_eq!(
+            brent(5, next, |state| *state),
+            Cycle {
+                start: 0,
+                period: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_cycle_detector() {
+        let mut detector = CycleDetector::new();
+        let mut state = 0;
+        let cycle = loop {
+            if let Some(cycle) = detector.observe(state) {
+                break cycle;
+            }
+            state = next(&state);
+        };
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 3,
+                period: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let cycle = Cycle {
+            start: 3,
+            period: 4,
+        };
+        // The sum of the states up to each step grows by 3 + 4 + 5 + 6 with every pass.
+        let sums: Vec<u64> = (0..=7)
+            .scan((0, 0), |(state, sum), _| {
+                let current = *sum;
+                *sum += *state as u64;
+                *state = next(state);
+                Some(current)
+            })
+            .collect();
+        let simulated =
+            (0..1000).fold((0, 0), |(state, sum), _| (next(&state), sum + state as u64));
+
+        assert_eq!(cycle.delta(|step| sums[step]), 18);
+        assert_eq!(cycle.equivalent_step(12), 4);
+        assert_eq!(cycle.extrapolate(5, |step| sums[step]), 10);
+        assert_eq!(cycle.extrapolate(1000, |step| sums[step]), simulated.1);
+    }
+}
@@ -0,0 +1,394 @@
+//! A rectangular grid for the puzzles played out on a 2D map.
+//!
+//! Positions are `(row, column)` pairs counted from the top left corner, matching the order the
+//! map appears in the input. Directions are `(row, column)` offsets, see [`DIRECTIONS_4`] and
+//! [`DIRECTIONS_8`].
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+/// A `(row, column)` position.
+pub type Pos = (usize, usize);
+
+pub const NORTH: (isize, isize) = (-1, 0);
+pub const EAST: (isize, isize) = (0, 1);
+pub const SOUTH: (isize, isize) = (1, 0);
+pub const WEST: (isize, isize) = (0, -1);
+
+/// The orthogonal directions, clockwise from north.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [NORTH, EAST, SOUTH, WEST];
+
+/// The orthogonal and diagonal directions, clockwise from north.
+pub const DIRECTIONS_8: [(isize, isize); 8] =
+    [NORTH, (-1, 1), EAST, (1, 1), SOUTH, (1, -1), WEST, (-1, -1)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    /// A line of the map is longer or shorter than the first one.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        line: usize,
+        column: usize,
+        cell: char,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "the map is empty"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} is {} cells wide, expected {}",
+                line, found, expected
+            ),
+            GridError::InvalidCell { line, column, cell } => {
+                write!(
+                    f,
+                    "unexpected {:?} at line {}, column {}",
+                    cell, line, column
+                )
+            }
+        }
+    }
+}
+
+impl Error for GridError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` × `height` grid filled with `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// The grid mirrored along its main diagonal: rows become columns.
+    pub fn transpose(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|column| self.column(column).cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|column| self.column(column).rev().cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// The grid turned a quarter counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|column| self.column(column).cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row order. `None` if they don't fill whole rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parses a character map, one row per line. `cell` turns each character into a cell, or
+    /// returns `None` to reject it.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut cells = vec![];
+        let mut width = 0;
+        let mut height = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                let value = cell(c).ok_or(GridError::InvalidCell {
+                    line: index + 1,
+                    column: column + 1,
+                    cell: c,
+                })?;
+                cells.push(value);
+            }
+
+            let found = cells.len() - start;
+            if index == 0 {
+                width = found;
+            } else if found != width {
+                return Err(GridError::Ragged {
+                    line: index + 1,
+                    expected: width,
+                    found,
+                });
+            }
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Pos) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// The position one step from `pos` in `direction`, if it is on the grid.
+    pub fn step(&self, (row, column): Pos, (d_row, d_column): (isize, isize)) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            column.checked_add_signed(d_column)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The orthogonal neighbours of `pos` on the grid.
+    pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_4
+            .into_iter()
+            .filter_map(move |direction| self.step(pos, direction))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` on the grid.
+    pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_8
+            .into_iter()
+            .filter_map(move |direction| self.step(pos, direction))
+    }
+
+    /// The cells seen when walking from `pos` in `direction` up to the edge, `pos` excluded.
+    pub fn ray(&self, pos: Pos, direction: (isize, isize)) -> impl Iterator<Item = (Pos, &T)> {
+        std::iter::successors(self.step(pos, direction), move |&pos| {
+            self.step(pos, direction)
+        })
+        .map(|pos| (pos, &self[pos]))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, column: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[column..].iter().step_by(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    pub fn from_chars(input: &str) -> Result<Self, GridError> {
+        Self::parse(input, Some)
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a map of single digits, such as tree heights.
+    pub fn from_digits(input: &str) -> Result<Self, GridError> {
+        Self::parse(input, |c| c.to_digit(10).map(|digit| digit as u8))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "{:?} is outside of the {}x{} grid",
+                pos, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", pos, width, height))
+    }
+}
+
+/// Prints the grid one row per line, without separators between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::from_chars(MAP).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.to_string(), MAP);
+
+        assert_eq!(
+            Grid::from_chars("ab\nc\n"),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::from_digits("12\n3x\n"),
+            Err(GridError::InvalidCell {
+                line: 2,
+                column: 2,
+                cell: 'x'
+            })
+        );
+        assert_eq!(Grid::from_chars(""), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::from_chars(MAP).unwrap();
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours_4((1, 1)).count(), 3);
+        assert_eq!(
+            grid.neighbours_8((0, 1)).collect::<Vec<_>>(),
+            vec![(0, 2), (1, 2), (1, 1), (1, 0), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_rows_columns_and_rays() {
+        let grid = Grid::from_chars(MAP).unwrap();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(
+            grid.ray((0, 0), EAST).map(|(_, c)| c).collect::<String>(),
+            "bc"
+        );
+        assert_eq!(
+            grid.ray((1, 2), (-1, -1)).collect::<Vec<_>>(),
+            vec![((0, 1), &'b')]
+        );
+        assert_eq!(grid.ray((0, 0), NORTH).count(), 0);
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = Grid::from_chars(MAP).unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).to_string(),
+            "ABC\nDEF\n"
+        );
+    }
+}
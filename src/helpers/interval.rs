@@ -0,0 +1,263 @@
+//! Sets of integers stored as sorted, disjoint ranges, for puzzles about which parts of a line
+//! are covered.
+//!
+//! Ranges are half-open like Rust's `a..b`: an inclusive range `a..=b` from a puzzle is
+//! `a..b + 1`.
+use std::fmt::Debug;
+use std::ops::{Add, Range, Sub};
+
+/// The integer types an [`IntervalSet`] can hold.
+pub trait Integer: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Sorted, non-empty ranges with gaps between them.
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    /// Adds `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first < last {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Removes `range` from the set, splitting the ranges it cuts through.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+
+        let mut kept = vec![];
+        if self.ranges[first].start < range.start {
+            kept.push(self.ranges[first].start..range.start);
+        }
+        if range.end < self.ranges[last - 1].end {
+            kept.push(range.end..self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, kept);
+    }
+
+    /// The sorted, disjoint ranges of the set.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |len, range| len + (range.end - range.start))
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= value)
+    }
+
+    /// Whether every value of `range` is in the set.
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let index = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(index)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// Whether any value of `range` is in the set.
+    pub fn overlaps(&self, range: &Range<T>) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= range.start);
+        !range.is_empty() && self.ranges.get(index).is_some_and(|r| r.start < range.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let start = x.start.max(y.start);
+            let end = x.end.min(y.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// The part of the set within `bounds`.
+    pub fn clamp(&self, bounds: Range<T>) -> Self {
+        self.intersection(&Self::from(bounds))
+    }
+
+    /// The ranges within `bounds` that are not in the set, in order.
+    pub fn gaps(&self, bounds: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let mut start = bounds.start;
+        let end = bounds.end;
+        self.ranges
+            .iter()
+            .map(|range| range.start..range.end)
+            .chain([end..end])
+            .filter_map(move |range| {
+                let gap = start..range.start.min(end);
+                start = start.max(range.end);
+                (!gap.is_empty()).then_some(gap)
+            })
+    }
+}
+
+impl<T: Integer> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Integer> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut ranges: Vec<Range<T>> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<Range<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut set = IntervalSet::new();
+        set.insert(10..20);
+        set.insert(0..5);
+        set.insert(5..7);
+        set.insert(30..30);
+        assert_eq!(ranges(&set), vec![0..7, 10..20]);
+
+        set.insert(6..12);
+        assert_eq!(ranges(&set), vec![0..20]);
+        assert_eq!(set.len(), 20);
+
+        set.remove(3..5);
+        set.remove(18..25);
+        assert_eq!(ranges(&set), vec![0..3, 5..18]);
+        set.remove(-5..6);
+        assert_eq!(ranges(&set), vec![6..18]);
+
+        let collected: IntervalSet<i32> = [10..20, 0..5, 4..8, 20..21].into_iter().collect();
+        assert_eq!(ranges(&collected), vec![0..8, 10..21]);
+    }
+
+    #[test]
+    fn test_queries() {
+        let set: IntervalSet<i32> = [-2..3, 10..20].into_iter().collect();
+        assert!(set.contains(-2) && set.contains(19));
+        assert!(!set.contains(3) && !set.contains(20) && !set.contains(-3));
+        assert!(set.contains_range(&(11..20)));
+        assert!(!set.contains_range(&(0..11)));
+        assert!(set.overlaps(&(2..10)));
+        assert!(!set.overlaps(&(3..10)));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<i32> = [0..10, 20..30].into_iter().collect();
+        let b = IntervalSet::from(5..25);
+        assert_eq!(ranges(&a.union(&b)), vec![0..30]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![5..10, 20..25]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..5, 25..30]);
+        assert_eq!(ranges(&a.clamp(8..22)), vec![8..10, 20..22]);
+    }
+
+    #[test]
+    fn test_gaps() {
+        let set: IntervalSet<i32> = [0..10, 20..30].into_iter().collect();
+        assert_eq!(
+            set.gaps(-5..40).collect::<Vec<_>>(),
+            vec![-5..0, 10..20, 30..40]
+        );
+        assert_eq!(set.gaps(5..25).collect::<Vec<_>>(), vec![10..20]);
+        assert_eq!(set.gaps(0..10).count(), 0);
+        assert_eq!(
+            IntervalSet::new().gaps(1..4).collect::<Vec<_>>(),
+            vec![1..4]
+        );
+    }
+}
@@ -0,0 +1,372 @@
+//! Parsing puzzle inputs without a hand-written parser per day.
+//!
+//! Most inputs are lines or blank-line-separated blocks of a fixed shape, which [`scan`] and
+//! [`captures`] match against a template with `{}` placeholders:
+//!
+//! ```
+//! use advent_of_code::helpers::parse::{parse_lines, scan};
+//!
+//! let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n";
+//! let sensors = parse_lines(input, |line| {
+//!     scan::<i32, 4>("Sensor at x={}, y={}: closest beacon is at x={}, y={}", line)
+//! });
+//! assert_eq!(sensors, Ok(vec![[2, 18, -2, 15]]));
+//! ```
+//!
+//! Errors carry the line and column of the offending text, counted from 1. [`parse_lines`] and
+//! [`parse_blocks`] number them from the start of the whole input.
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error at byte `offset` of `text`.
+    pub fn at(text: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    /// The same error in a text that starts `lines` lines further down.
+    fn shifted(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// The text matched by a placeholder, which remembers where it was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field<'t> {
+    source: &'t str,
+    offset: usize,
+    len: usize,
+}
+
+impl<'t> Field<'t> {
+    pub fn as_str(&self) -> &'t str {
+        &self.source[self.offset..self.offset + self.len]
+    }
+
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        self.as_str().parse().map_err(|_| {
+            self.error(format!(
+                "expected {}, found {:?}",
+                type_name::<T>(),
+                self.as_str()
+            ))
+        })
+    }
+
+    /// The parts of the field between occurrences of `separator`, like [`str::split`].
+    pub fn split(&self, separator: &str) -> Vec<Field<'t>> {
+        let mut offset = self.offset;
+        self.as_str()
+            .split(separator)
+            .map(|part| {
+                let field = Field {
+                    source: self.source,
+                    offset,
+                    len: part.len(),
+                };
+                offset += part.len() + separator.len();
+                field
+            })
+            .collect()
+    }
+
+    /// All integers in the field, see [`integers`].
+    pub fn integers<T: FromStr>(&self) -> Vec<T> {
+        integers(self.as_str())
+    }
+
+    /// An error pointing at the start of the field.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.source, self.offset, message)
+    }
+}
+
+/// All integers in `text`, in order. A `-` directly before a number is its sign unless it
+/// follows a digit, so ranges like `2-4` read as two numbers. Numbers that don't fit `T`, such
+/// as negative ones for unsigned types, are skipped.
+pub fn integers<T: FromStr>(text: &str) -> Vec<T> {
+    let bytes = text.as_bytes();
+    let mut numbers = vec![];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let negative = bytes[index] == b'-'
+            && bytes.get(index + 1).is_some_and(u8::is_ascii_digit)
+            && (index == 0 || !bytes[index - 1].is_ascii_digit());
+        if !negative && !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        index += 1;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        if let Ok(number) = text[start..index].parse() {
+            numbers.push(number);
+        }
+    }
+
+    numbers
+}
+
+/// Matches `text` against `template`, where each `{}` matches any text up to the literal text
+/// that follows it in the template. Returns the text of the `N` placeholders. The whole of
+/// `text` has to match, and templates can span several lines.
+///
+/// # Panics
+/// If `template` doesn't have `N` placeholders, or has two placeholders next to each other.
+pub fn captures<'t, const N: usize>(
+    template: &str,
+    text: &'t str,
+) -> Result<[Field<'t>; N], ParseError> {
+    let mut literals = template.split("{}");
+    let first = literals.next().unwrap();
+    let literals: Vec<&str> = literals.collect();
+    assert_eq!(
+        literals.len(),
+        N,
+        "{:?} should have {} placeholders",
+        template,
+        N
+    );
+
+    let mut offset = expect_literal(text, 0, first)?;
+    let mut fields = [Field {
+        source: text,
+        offset: 0,
+        len: 0,
+    }; N];
+
+    for (index, literal) in literals.iter().enumerate() {
+        let rest = &text[offset..];
+        let len = if index == N - 1 {
+            // The last placeholder takes everything up to the literal the text ends with.
+            match rest.strip_suffix(literal) {
+                Some(field) => field.len(),
+                None => {
+                    return Err(ParseError::at(
+                        text,
+                        offset,
+                        format!("expected a value followed by {:?}", literal),
+                    ))
+                }
+            }
+        } else {
+            assert!(
+                !literal.is_empty(),
+                "{:?} has two placeholders next to each other",
+                template
+            );
+            match rest.find(literal) {
+                Some(len) => len,
+                None => {
+                    return Err(ParseError::at(
+                        text,
+                        offset,
+                        format!("expected a value followed by {:?}", literal),
+                    ))
+                }
+            }
+        };
+
+        fields[index] = Field {
+            source: text,
+            offset,
+            len,
+        };
+        offset += len + literal.len();
+    }
+
+    if N == 0 && offset < text.len() {
+        return Err(ParseError::at(text, offset, "unexpected text"));
+    }
+    Ok(fields)
+}
+
+/// Checks that `literal` is found at `offset` of `text` and returns the offset after it.
+fn expect_literal(text: &str, offset: usize, literal: &str) -> Result<usize, ParseError> {
+    let rest = &text[offset..];
+    if rest.starts_with(literal) {
+        return Ok(offset + literal.len());
+    }
+
+    let matching: usize = rest
+        .chars()
+        .zip(literal.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+    Err(ParseError::at(
+        text,
+        offset + matching,
+        format!("expected {:?}", literal),
+    ))
+}
+
+/// Like [`captures`], then parses every placeholder as a `T`.
+pub fn scan<T: FromStr, const N: usize>(template: &str, text: &str) -> Result<[T; N], ParseError> {
+    let values = captures::<N>(template, text)?
+        .iter()
+        .map(Field::parse)
+        .collect::<Result<Vec<T>, _>>()?;
+    match values.try_into() {
+        Ok(values) => Ok(values),
+        Err(_) => unreachable!("there is a value for each placeholder"),
+    }
+}
+
+/// The blocks of lines separated by blank lines, each with the number of its first line.
+pub fn blocks(input: &str) -> Vec<(usize, &str)> {
+    let mut blocks = vec![];
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    let mut end = 0;
+
+    for (index, line) in input.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((first_line, start)) = current.take() {
+                blocks.push((first_line, &input[start..end]));
+            }
+        } else {
+            current.get_or_insert((index + 1, offset));
+            end = offset + line.trim_end_matches(['\r', '\n']).len();
+        }
+        offset += line.len();
+    }
+    if let Some((first_line, start)) = current {
+        blocks.push((first_line, &input[start..end]));
+    }
+
+    blocks
+}
+
+/// Parses every line with `parse`, skipping blank lines.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| parse(line).map_err(|e| e.shifted(index)))
+        .collect()
+}
+
+/// Parses every blank-line-separated block with `parse`.
+pub fn parse_blocks<'a, T>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    blocks(input)
+        .into_iter()
+        .map(|(first_line, block)| parse(block).map_err(|e| e.shifted(first_line - 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(
+            integers::<i32>("x=-3, y=14: 2-4 and -x -7"),
+            vec![-3, 14, 2, 4, -7]
+        );
+        assert_eq!(integers::<u8>("1, -2, 300, 4"), vec![1, 4]);
+        assert!(integers::<u32>("none").is_empty());
+    }
+
+    #[test]
+    fn test_scan() {
+        let template = "move {} from {} to {}";
+        assert_eq!(scan(template, "move 1 from 2 to 3"), Ok([1, 2, 3]));
+        assert_eq!(
+            scan::<u32, 3>(template, "move 1 form 2 to 3"),
+            Err(ParseError {
+                line: 1,
+                column: 6,
+                message: "expected a value followed by \" from \"".into()
+            })
+        );
+        assert_eq!(
+            scan::<u32, 3>(template, "move 1 from x to 3").unwrap_err(),
+            ParseError {
+                line: 1,
+                column: 13,
+                message: "expected u32, found \"x\"".into()
+            }
+        );
+        assert_eq!(
+            scan::<u32, 3>(template, "mv 1 from 2 to 3")
+                .unwrap_err()
+                .column,
+            2
+        );
+        assert!(scan::<u32, 1>("{} items.", "3 items").is_err());
+    }
+
+    #[test]
+    fn test_captures() {
+        let template = "Monkey {}:\n  Operation: new = {} {} {}";
+        let [monkey, left, op, right] =
+            captures(template, "Monkey 0:\n  Operation: new = old * 19").unwrap();
+        assert_eq!(monkey.parse::<usize>(), Ok(0));
+        assert_eq!(
+            [left.as_str(), op.as_str(), right.as_str()],
+            ["old", "*", "19"]
+        );
+        assert_eq!((op.error("bad").line, op.error("bad").column), (2, 24));
+
+        let [items] = captures("Starting items: {}", "Starting items: 79, x8").unwrap();
+        let items = items.split(", ");
+        assert_eq!(items[0].parse::<u64>(), Ok(79));
+        assert_eq!(items[1].parse::<u64>().unwrap_err().column, 21);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "a\nb\n\n\nc\r\n\r\nd\n";
+        assert_eq!(blocks(input), vec![(1, "a\nb"), (5, "c"), (7, "d")]);
+
+        let error = parse_blocks(input, |block| {
+            captures::<1>("{}\nb", block).map(|[field]| field.as_str())
+        })
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
+
+        let error = parse_lines("1\n\n2\nx\n", |line| scan::<u8, 1>("{}", line)).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+    }
+}
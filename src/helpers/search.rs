@@ -0,0 +1,297 @@
+//! Shortest paths over graphs given as a neighbour function, so the graph never has to be built:
+//! a node's neighbours are computed when the search reaches it.
+//!
+//! All searches accept several start nodes, which is the same as searching from a virtual node
+//! connected to each of them. To find the closest of many sources to a single target, search
+//! backwards from the target instead, or from all sources at once.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The costs of paths. Implemented for the unsigned and signed integer types.
+pub trait Cost: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! impl_cost {
+    ($($t:ty),*) => {
+        $(impl Cost for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_cost!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The result of exploring a graph: the distance of every reached node from the closest start,
+/// and the node it was reached from.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Paths<N, C> {
+    /// The distance of `node` from the closest start, `None` if it can't be reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// A shortest path from one of the starts to `node`, both included.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every reached node with its distance, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.distances
+            .iter()
+            .map(|(node, distance)| (node, *distance))
+    }
+}
+
+/// Breadth-first search for graphs where every edge has the same cost. Reaches every node
+/// reachable from `starts`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Paths<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths {
+        distances: HashMap::new(),
+        parents: HashMap::new(),
+    };
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if paths.distances.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let distance = paths.distances[&node] + 1;
+        for next in neighbours(&node) {
+            if !paths.distances.contains_key(&next) {
+                paths.distances.insert(next.clone(), distance);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's algorithm for graphs with non-negative edge costs. `neighbours` returns each
+/// neighbour with the cost of the edge to it. Reaches every node reachable from `starts`.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Paths<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        distances: HashMap::new(),
+        parents: HashMap::new(),
+    };
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        paths.distances.insert(start.clone(), C::ZERO);
+        heap.push(Reverse((C::ZERO, start)));
+    }
+
+    while let Some(Reverse((distance, node))) = heap.pop() {
+        if paths.distances[&node] < distance {
+            // A shorter path to the node was found after this entry was queued.
+            continue;
+        }
+        for (next, cost) in neighbours(&node) {
+            let next_distance = distance + cost;
+            if paths
+                .distances
+                .get(&next)
+                .is_none_or(|&known| next_distance < known)
+            {
+                paths.distances.insert(next.clone(), next_distance);
+                paths.parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_distance, next)));
+            }
+        }
+    }
+
+    paths
+}
+
+/// A* search for the cheapest path from one of `starts` to a node for which `is_goal` holds.
+/// `heuristic` estimates the remaining cost from a node and must never overestimate it, or the
+/// path found may not be the cheapest. Returns the path, starts and goal included, and its cost.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        distances: HashMap::new(),
+        parents: HashMap::new(),
+    };
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        paths.distances.insert(start.clone(), C::ZERO);
+        heap.push(Reverse((heuristic(&start), C::ZERO, start)));
+    }
+
+    while let Some(Reverse((_, distance, node))) = heap.pop() {
+        if paths.distances[&node] < distance {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((paths.path(&node)?, distance));
+        }
+        for (next, cost) in neighbours(&node) {
+            let next_distance = distance + cost;
+            if paths
+                .distances
+                .get(&next)
+                .is_none_or(|&known| next_distance < known)
+            {
+                paths.distances.insert(next.clone(), next_distance);
+                paths.parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((
+                    heuristic(&next) + next_distance,
+                    next_distance,
+                    next,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+/// The Floyd–Warshall algorithm: the distances between all pairs of the nodes `0..nodes`, given
+/// the directed `(from, to, cost)` edges. `distances[a][b]` is `None` if `b` can't be reached from
+/// `a`. Takes O(nodes³) time, so it suits small, dense graphs.
+pub fn floyd_warshall<C: Cost>(
+    nodes: usize,
+    edges: impl IntoIterator<Item = (usize, usize, C)>,
+) -> Vec<Vec<Option<C>>> {
+    let mut distances = vec![vec![None; nodes]; nodes];
+    for (node, row) in distances.iter_mut().enumerate() {
+        row[node] = Some(C::ZERO);
+    }
+    for (from, to, cost) in edges {
+        let known = &mut distances[from][to];
+        if known.is_none_or(|known| cost < known) {
+            *known = Some(cost);
+        }
+    }
+
+    for via in 0..nodes {
+        // Paths through `via` don't shorten the paths from `via` itself.
+        let from_via = distances[via].clone();
+        for row in distances.iter_mut() {
+            let Some(first) = row[via] else {
+                continue;
+            };
+            for (known, second) in row.iter_mut().zip(&from_via) {
+                let Some(second) = second else {
+                    continue;
+                };
+                let distance = first + *second;
+                if known.is_none_or(|known| distance < known) {
+                    *known = Some(distance);
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 → 1 → 2 → 3 with a shortcut 0 → 2 that is cheaper in hops but not in cost.
+    fn edges(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let paths = bfs([0], |node| edges(node).into_iter().map(|(next, _)| next));
+        assert_eq!(paths.distance(&3), Some(2));
+        assert_eq!(paths.path(&3), Some(vec![0, 2, 3]));
+        assert_eq!(paths.distance(&4), None);
+
+        let paths = bfs([0, 2], |node| edges(node).into_iter().map(|(next, _)| next));
+        assert_eq!(paths.distance(&3), Some(1));
+        assert_eq!(paths.path(&2), Some(vec![2]));
+        assert_eq!(paths.iter().count(), 4);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let paths = dijkstra([0], edges);
+        assert_eq!(paths.distance(&3), Some(3));
+        assert_eq!(paths.path(&3), Some(vec![0, 1, 2, 3]));
+
+        let paths = dijkstra([1, 0], edges);
+        assert_eq!(paths.distance(&1), Some(0));
+        assert_eq!(paths.path(&3), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_astar() {
+        // Walking on a line towards 10, one step at a time or jumping 3 at a higher cost.
+        let found = astar(
+            [0_i32],
+            |&n| [(n + 1, 2), (n + 3, 5), (n - 1, 2)],
+            |&n| (10 - n).max(0),
+            |&n| n == 10,
+        );
+        let (path, cost) = found.unwrap();
+        assert_eq!(cost, 17);
+        assert_eq!(path.first(), Some(&0));
+        assert_eq!(path.last(), Some(&10));
+
+        assert_eq!(astar([0], edges, |_| 0, |&n| n == 4), None);
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let all_edges = (0..4).flat_map(|from| {
+            edges(&from)
+                .into_iter()
+                .map(move |(to, cost)| (from as usize, to as usize, cost))
+        });
+        let distances = floyd_warshall(4, all_edges);
+        assert_eq!(distances[0][3], Some(3));
+        assert_eq!(distances[1][1], Some(0));
+        assert_eq!(distances[3][0], None);
+    }
+}
@@ -0,0 +1,200 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Just enough JSON to read and write the flat, one-object-per-line records the template emits.
+//! Nested arrays and objects are not supported.
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// Numbers are kept as their source text so callers can parse them into the type they need.
+    Number(String),
+    String(String),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn parse_number<T: std::str::FromStr>(&self) -> Option<T> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+pub type Object = HashMap<String, Value>;
+
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Serializes key/value pairs (values already encoded) into a single-line JSON object.
+pub fn object(fields: &[(&str, String)]) -> String {
+    let body: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{}", escape(key), value))
+        .collect();
+    format!("{{{}}}", body.join(","))
+}
+
+/// Parses a single flat JSON object. Returns `None` for anything else.
+pub fn parse_object(line: &str) -> Option<Object> {
+    let mut chars = line.trim().chars().peekable();
+    let mut object = Object::new();
+
+    if chars.next()? != '{' {
+        return None;
+    }
+
+    skip_whitespace(&mut chars);
+    if chars.peek() == Some(&'}') {
+        chars.next();
+        return chars.next().is_none().then_some(object);
+    }
+
+    loop {
+        skip_whitespace(&mut chars);
+        let key = parse_string(&mut chars)?;
+        skip_whitespace(&mut chars);
+        if chars.next()? != ':' {
+            return None;
+        }
+        skip_whitespace(&mut chars);
+        let value = parse_value(&mut chars)?;
+        object.insert(key, value);
+        skip_whitespace(&mut chars);
+        match chars.next()? {
+            ',' => continue,
+            '}' => break,
+            _ => return None,
+        }
+    }
+
+    chars.next().is_none().then_some(object)
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+fn skip_whitespace(chars: &mut Chars) {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn parse_value(chars: &mut Chars) -> Option<Value> {
+    match chars.peek()? {
+        '"' => parse_string(chars).map(Value::String),
+        't' => parse_literal(chars, "true").map(|_| Value::Bool(true)),
+        'f' => parse_literal(chars, "false").map(|_| Value::Bool(false)),
+        'n' => parse_literal(chars, "null").map(|_| Value::Null),
+        _ => {
+            let mut number = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E') {
+                    number.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            (!number.is_empty()).then_some(Value::Number(number))
+        }
+    }
+}
+
+fn parse_literal(chars: &mut Chars, literal: &str) -> Option<()> {
+    for expected in literal.chars() {
+        if chars.next()? != expected {
+            return None;
+        }
+    }
+    Some(())
+}
+
+fn parse_string(chars: &mut Chars) -> Option<String> {
+    if chars.next()? != '"' {
+        return None;
+    }
+
+    let mut out = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(out),
+            '\\' => match chars.next()? {
+                '"' => out.push('"'),
+                '\\' => out.push('\\'),
+                '/' => out.push('/'),
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                'b' => out.push('\u{8}'),
+                'f' => out.push('\u{c}'),
+                'u' => {
+                    let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+                    out.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                _ => return None,
+            },
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let line = object(&[
+            ("answer", escape("#..#\n\"quoted\"\t\\")),
+            ("day", 10.to_string()),
+            ("solved", true.to_string()),
+            ("missing", "null".into()),
+        ]);
+        let parsed = parse_object(&line).unwrap();
+
+        assert_eq!(parsed["answer"].as_str(), Some("#..#\n\"quoted\"\t\\"));
+        assert_eq!(parsed["day"].parse_number::<u8>(), Some(10));
+        assert_eq!(parsed["solved"].as_bool(), Some(true));
+        assert_eq!(parsed["missing"], Value::Null);
+    }
+
+    #[test]
+    fn test_rejects_non_objects() {
+        assert_eq!(parse_object("🎄 Part 1 🎄"), None);
+        assert_eq!(parse_object("{\"a\": 1"), None);
+        assert_eq!(parse_object("{\"a\": 1} trailing"), None);
+        assert_eq!(parse_object("{}"), Some(Object::new()));
+    }
+}
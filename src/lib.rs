@@ -4,172 +4,277 @@
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
 use std::env;
+use std::error::Error;
+use std::fmt::Display;
 use std::fs;
-
+use std::io;
+use std::panic::Location;
+use std::path::{Component, Path, PathBuf};
+
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod config;
+pub mod examples;
 pub mod helpers;
+pub mod json;
+pub mod params;
+pub mod puzzle;
+pub mod readme;
+pub mod render;
+pub mod runner;
+pub mod session;
+pub mod submit;
+pub mod toml;
+pub mod unlock;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// `code` if colored output is enabled in the config, otherwise nothing.
+pub fn ansi(code: &'static str) -> &'static str {
+    if config::get().use_color() {
+        code
+    } else {
+        ""
+    }
+}
+
+/// Runs and prints one part. The year and day come from the solution's location in
+/// `years/<year>/bin/NN.rs`. Without an input argument, the day's input is loaded once from
+/// `--input <path>`, `--input -` (stdin), `$AOC_INPUT_DIR` or `years/<year>/inputs`, in that order.
+/// Parameterised solutions (see [`params`]) are run with `solve!(1, part_one, params: Params)`.
 #[macro_export]
 macro_rules! solve {
-    ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
-        }
+    ($part:expr, $solver:ident, params: $params:ty) => {{
+        let params = <$params as advent_of_code::params::Params>::from_args().unwrap_or_else(|e| {
+            eprintln!("Failed to process arguments: {}", e);
+            std::process::exit(1);
+        });
+        advent_of_code::solve!($part, |input| $solver(input, &params));
+    }};
+    ($part:expr, $solver:expr) => {{
+        use advent_of_code::runner::{input_or_exit, year_and_day_from_path};
+
+        let (year, day) = year_and_day_from_path(file!());
+        advent_of_code::solve!($part, $solver, input_or_exit(year, day));
+    }};
+    ($part:expr, $solver:expr, $input:expr) => {{
+        use advent_of_code::runner::{solve, year_and_day_from_path};
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        let (year, day) = year_and_day_from_path(file!());
+        solve(year, day, $part, $solver, $input);
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
+/// Environment variable that points `read_file("inputs", ..)` at a different directory.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
 
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
+#[derive(Debug)]
+pub struct ReadFileError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not read \"{}\": {}",
+            self.path.display(),
+            self.source
+        )
+    }
 }
 
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
+impl Error for ReadFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
 }
 
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
-        }
-    })
+/// The directory containing `Cargo.toml`. Paths are resolved from here, not from the cwd.
+pub fn project_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+/// The directory holding a year's solutions and data: `years/<year>`.
+pub fn year_dir(year: u16) -> PathBuf {
+    project_root().join("years").join(year.to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
-                ANSI_RESET, ANSI_RESET
-            )),
-            0_f64
-        );
+/// The year of a file inside `years/<year>/`, e.g. the path from `file!()`.
+pub fn year_from_path(path: &str) -> Option<u16> {
+    let mut components = Path::new(path).components();
+    components.find(|c| *c == Component::Normal("years".as_ref()))?;
+    components.next()?.as_os_str().to_str()?.parse().ok()
+}
 
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
+/// `<folder>/NN.txt` for a year, with the folder's directory taken from the config.
+pub fn input_path(year: u16, folder: &str, day: u8) -> PathBuf {
+    let filename = format!("{:02}.txt", day);
+    match env::var_os(INPUT_DIR_ENV) {
+        Some(dir) if folder == "inputs" => PathBuf::from(dir).join(filename),
+        _ => config::get().dir(folder, year).join(filename),
+    }
+}
 
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
-        );
+pub fn try_read_file(year: u16, folder: &str, day: u8) -> Result<String, ReadFileError> {
+    let path = input_path(year, folder, day);
+    fs::read_to_string(&path).map_err(|source| ReadFileError { path, source })
+}
 
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
-        );
-    }
+/// Reads `<folder>/NN.txt` (see [`input_path`]) for the year of the calling solution. Outside of
+/// `years/`, the default year from `aoc.toml` is used.
+#[track_caller]
+pub fn read_file(folder: &str, day: u8) -> String {
+    let year = match year_from_path(Location::caller().file()) {
+        Some(year) => year,
+        None => config::default_year().unwrap_or_else(|e| panic!("{}", e)),
+    };
+    try_read_file(year, folder, day).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Exit codes of the `download` and `read` binaries.
+pub mod exit_code {
+    /// Invalid arguments, or any failure not listed below.
+    pub const FAILURE: i32 = 1;
+    /// aoc-cli is not installed or could not be started.
+    pub const COMMAND_NOT_FOUND: i32 = 2;
+    /// The session cookie is missing, invalid or expired.
+    pub const SESSION: i32 = 3;
+    /// The puzzle is not unlocked yet.
+    pub const NOT_UNLOCKED: i32 = 4;
+    /// The site could not be reached.
+    pub const NETWORK: i32 = 5;
+    /// Reading or writing a local file failed.
+    pub const IO: i32 = 6;
 }
 
 pub mod aoc_cli {
+    use crate::session::{self, SessionError, SessionFile};
+    use crate::{config, exit_code};
     use std::{
+        error::Error,
         fmt::Display,
         fs::create_dir_all,
-        process::{Command, Output, Stdio},
+        io::{self, Write},
+        path::{Path, PathBuf},
+        process::{Command, ExitStatus, Output, Stdio},
     };
 
+    #[derive(Debug)]
     pub enum AocCliError {
-        CommandNotFound,
-        CommandNotCallable,
-        BadExitStatus(Output),
-        IoError,
+        CommandNotFound {
+            command: String,
+            source: io::Error,
+        },
+        CommandNotCallable {
+            command: String,
+            source: io::Error,
+        },
+        /// aoc-cli found no session cookie, or the site did not accept it.
+        Session {
+            stderr: String,
+        },
+        NotUnlocked {
+            stderr: String,
+        },
+        BadExitStatus {
+            status: ExitStatus,
+            stderr: String,
+        },
+        Io {
+            path: PathBuf,
+            source: io::Error,
+        },
+        /// The session cookie set up for this project could not be read.
+        SessionLookup(SessionError),
     }
 
     impl Display for AocCliError {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
-                AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-                AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-                AocCliError::BadExitStatus(_) => {
-                    write!(f, "aoc-cli exited with a non-zero status.")
+                AocCliError::CommandNotFound { command, .. } => write!(
+                    f,
+                    "command \"{}\" not found. Try running \"cargo install aoc-cli\" to install it.",
+                    command
+                ),
+                AocCliError::CommandNotCallable { command, source } => {
+                    write!(f, "command \"{}\" could not be called: {}", command, source)
+                }
+                AocCliError::Session { stderr } => write!(
+                    f,
+                    "aoc-cli has no valid session cookie. Log in again and update it.\n{}",
+                    stderr.trim_end()
+                ),
+                AocCliError::NotUnlocked { stderr } => {
+                    write!(f, "the puzzle is not unlocked yet.\n{}", stderr.trim_end())
+                }
+                AocCliError::BadExitStatus { status, stderr } => {
+                    write!(f, "aoc-cli failed ({}).\n{}", status, stderr.trim_end())
                 }
-                AocCliError::IoError => write!(f, "could not write output files to file system."),
+                AocCliError::Io { path, source } => {
+                    write!(f, "could not write to \"{}\": {}", path.display(), source)
+                }
+                AocCliError::SessionLookup(e) => e.fmt(f),
+            }
+        }
+    }
+
+    impl Error for AocCliError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                AocCliError::CommandNotFound { source, .. }
+                | AocCliError::CommandNotCallable { source, .. }
+                | AocCliError::Io { source, .. } => Some(source),
+                AocCliError::SessionLookup(e) => e.source(),
+                _ => None,
+            }
+        }
+    }
+
+    impl AocCliError {
+        pub fn exit_code(&self) -> i32 {
+            match self {
+                AocCliError::CommandNotFound { .. } | AocCliError::CommandNotCallable { .. } => {
+                    exit_code::COMMAND_NOT_FOUND
+                }
+                AocCliError::Session { .. } | AocCliError::SessionLookup(_) => exit_code::SESSION,
+                AocCliError::NotUnlocked { .. } => exit_code::NOT_UNLOCKED,
+                AocCliError::BadExitStatus { .. } => exit_code::FAILURE,
+                AocCliError::Io { .. } => exit_code::IO,
             }
         }
     }
 
     pub fn check() -> Result<(), AocCliError> {
-        Command::new("aoc")
+        let command = &config::get().aoc_cli_command;
+        Command::new(command)
             .arg("-V")
             .output()
-            .map_err(|_| AocCliError::CommandNotFound)?;
+            .map_err(|source| spawn_error(command, source))?;
         Ok(())
     }
 
-    pub fn read(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        // TODO: output local puzzle if present.
+    pub fn read(day: u8, year: u16) -> Result<Output, AocCliError> {
         let args = build_args("read", &[], day, year);
         call_aoc_cli(&args)
     }
 
-    pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        let input_path = get_input_path(day);
+    pub fn download(day: u8, year: u16) -> Result<Output, AocCliError> {
+        let input_path = get_input_path(year, day);
 
-        let puzzle_path = get_puzzle_path(day);
-        create_dir_all("src/puzzles").map_err(|_| AocCliError::IoError)?;
+        let puzzle_path = get_puzzle_path(year, day);
+        for path in [&input_path, &puzzle_path] {
+            if let Some(dir) = Path::new(path).parent() {
+                create_dir_all(dir).map_err(|source| AocCliError::Io {
+                    path: dir.to_path_buf(),
+                    source,
+                })?;
+            }
+        }
 
         let args = build_args(
             "download",
@@ -186,49 +291,121 @@ pub mod aoc_cli {
 
         let output = call_aoc_cli(&args)?;
 
-        if output.status.success() {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-            Ok(output)
-        } else {
-            Err(AocCliError::BadExitStatus(output))
-        }
+        println!("---");
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(output)
     }
 
-    fn get_input_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("src/inputs/{}.txt", day_padded)
+    fn get_input_path(year: u16, day: u8) -> String {
+        crate::input_path(year, "inputs", day).display().to_string()
     }
 
-    fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("src/puzzles/{}.md", day_padded)
+    fn get_puzzle_path(year: u16, day: u8) -> String {
+        crate::client::puzzle_path(year, day).display().to_string()
     }
 
-    fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
+    fn build_args(command: &str, args: &[String], day: u8, year: u16) -> Vec<String> {
         let mut cmd_args = args.to_vec();
 
-        if let Some(year) = year {
-            cmd_args.push("--year".into());
-            cmd_args.push(year.to_string());
-        }
+        cmd_args.push("--year".into());
+        cmd_args.push(year.to_string());
 
         cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
         cmd_args
     }
 
+    fn spawn_error(command: &str, source: io::Error) -> AocCliError {
+        let command = command.to_string();
+        if source.kind() == io::ErrorKind::NotFound {
+            AocCliError::CommandNotFound { command, source }
+        } else {
+            AocCliError::CommandNotCallable { command, source }
+        }
+    }
+
+    /// Tells the failures worth reacting to apart by aoc-cli's error message.
+    fn classify(status: ExitStatus, stderr: String) -> AocCliError {
+        let message = stderr.to_lowercase();
+        if message.contains("session") || message.contains("log in") {
+            AocCliError::Session { stderr }
+        } else if message.contains("locked") || message.contains("not available yet") {
+            AocCliError::NotUnlocked { stderr }
+        } else {
+            AocCliError::BadExitStatus { status, stderr }
+        }
+    }
+
+    /// The session cookie for aoc-cli's `--session-file`. Without one, aoc-cli falls back to its
+    /// own lookup.
+    fn session_file() -> Result<Option<SessionFile>, AocCliError> {
+        match session::resolve() {
+            Ok(session) => SessionFile::new(&session)
+                .map(Some)
+                .map_err(AocCliError::SessionLookup),
+            Err(SessionError::Missing) => Ok(None),
+            Err(e) => Err(AocCliError::SessionLookup(e)),
+        }
+    }
+
+    /// Runs aoc-cli with its output passed through. Stderr is also captured to classify failures.
     fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
-        if cfg!(debug_assertions) {
-            println!("Calling >aoc with: {}", args.join(" "));
+        let command = &config::get().aoc_cli_command;
+        let session_file = session_file()?;
+
+        let mut args = args.to_vec();
+        if let Some(file) = &session_file {
+            let path = file.path.display().to_string();
+            args.splice(0..0, ["--session-file".to_string(), path]);
+        }
+
+        if config::get().verbose {
+            println!("Calling >{} with: {}", command, args.join(" "));
         }
 
-        Command::new("aoc")
-            .args(args)
+        let output = Command::new(command)
+            .args(&args)
             .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
+            .stderr(Stdio::piped())
             .output()
-            .map_err(|_| AocCliError::CommandNotCallable)
+            .map_err(|source| spawn_error(command, source))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        if output.status.success() {
+            // aoc-cli logs progress to stderr.
+            let _ = io::stderr().write_all(stderr.as_bytes());
+            Ok(output)
+        } else {
+            Err(classify(output.status, stderr))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn failed() -> ExitStatus {
+            Command::new("false").status().unwrap()
+        }
+
+        #[test]
+        fn test_classify() {
+            let session = classify(failed(), "Error: Invalid session cookie".into());
+            assert_eq!(session.exit_code(), exit_code::SESSION);
+
+            let locked = classify(failed(), "Error: Puzzle 25 of 2022 is still locked".into());
+            assert_eq!(locked.exit_code(), exit_code::NOT_UNLOCKED);
+
+            let other = classify(failed(), "Error: HTTP request failed".into());
+            assert!(matches!(other, AocCliError::BadExitStatus { .. }));
+        }
+
+        #[test]
+        fn test_spawn_error() {
+            let error = spawn_error("aoc", io::Error::from(io::ErrorKind::NotFound));
+            assert_eq!(error.exit_code(), exit_code::COMMAND_NOT_FOUND);
+            assert!(error.source().is_some());
+        }
     }
 }
@@ -2,47 +2,480 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::answers::{self, Verdict};
+use advent_of_code::bench::{bench, history, print_table, BenchOptions};
+use advent_of_code::client::Client;
+use advent_of_code::runner::{
+    load_input, parse_day, parse_days, positional_args, run_part, Outcome, OutputFormat,
+    PartResult, Solution,
+};
+use advent_of_code::submit::{self, Guess, Response};
+use advent_of_code::{ansi, config, readme, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::fs;
+use std::process;
+use std::time::Duration;
 
-fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
+/// Links every day's module into this binary and lists it in `solutions()`.
+/// Register a new day by adding a line here, e.g.
+/// `(2022, 21) => y2022_day21 in "../years/2022/bin/21.rs"`. Parameterised days name their params
+/// struct, e.g. `... in "../years/2022/bin/15.rs" with Params`, and are run with its defaults and
+/// any overrides from the command line.
+///
+/// The modules are left out of this binary's tests: each day's tests already run in its own
+/// `<year>-<day>` binary.
+macro_rules! solutions {
+    ($(($year:literal, $day:literal) => $module:ident in $path:literal $(with $params:ident)?),* $(,)?) => {
+        $(
+            #[cfg(not(test))]
+            #[allow(dead_code)]
+            #[path = $path]
+            mod $module;
+        )*
 
-            let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
-                args.push("--release");
-            }
+        #[cfg(not(test))]
+        fn solutions() -> Vec<Solution> {
+            vec![$(
+                solutions!(@solution $year, $day, $module $(, $params)?),
+            )*]
+        }
 
-            let cmd = Command::new("cargo").args(&args).output().unwrap();
+        #[cfg(test)]
+        fn solutions() -> Vec<Solution> {
+            vec![]
+        }
+    };
+    (@solution $year:literal, $day:literal, $module:ident) => {
+        Solution {
+            year: $year,
+            day: $day,
+            part_one: |input| $module::part_one(input).map(|answer| answer.to_string()),
+            part_two: |input| $module::part_two(input).map(|answer| answer.to_string()),
+        }
+    };
+    (@solution $year:literal, $day:literal, $module:ident, $params:ident) => {
+        Solution {
+            year: $year,
+            day: $day,
+            part_one: |input| {
+                $module::part_one(input, solutions!(@params $module::$params))
+                    .map(|answer| answer.to_string())
+            },
+            part_two: |input| {
+                $module::part_two(input, solutions!(@params $module::$params))
+                    .map(|answer| answer.to_string())
+            },
+        }
+    };
+    (@params $params:ty) => {{
+        static PARAMS: std::sync::OnceLock<$params> = std::sync::OnceLock::new();
+        PARAMS.get_or_init(|| {
+            <$params as advent_of_code::params::Params>::from_args().unwrap_or_else(|e| {
+                eprintln!("Failed to process arguments: {}", e);
+                process::exit(1);
+            })
+        })
+    }};
+}
 
-            println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
-            println!("----------");
+solutions! {
+    (2022, 1) => y2022_day01 in "../years/2022/bin/01.rs",
+    (2022, 2) => y2022_day02 in "../years/2022/bin/02.rs",
+    (2022, 3) => y2022_day03 in "../years/2022/bin/03.rs",
+    (2022, 4) => y2022_day04 in "../years/2022/bin/04.rs",
+    (2022, 5) => y2022_day05 in "../years/2022/bin/05.rs",
+    (2022, 6) => y2022_day06 in "../years/2022/bin/06.rs",
+    (2022, 7) => y2022_day07 in "../years/2022/bin/07.rs",
+    (2022, 8) => y2022_day08 in "../years/2022/bin/08.rs",
+    (2022, 9) => y2022_day09 in "../years/2022/bin/09.rs",
+    (2022, 10) => y2022_day10 in "../years/2022/bin/10.rs",
+    (2022, 11) => y2022_day11 in "../years/2022/bin/11.rs",
+    (2022, 12) => y2022_day12 in "../years/2022/bin/12.rs",
+    (2022, 13) => y2022_day13 in "../years/2022/bin/13.rs",
+    (2022, 14) => y2022_day14 in "../years/2022/bin/14.rs",
+    (2022, 15) => y2022_day15 in "../years/2022/bin/15.rs" with Params,
+    (2022, 16) => y2022_day16 in "../years/2022/bin/16.rs",
+    (2022, 17) => y2022_day17 in "../years/2022/bin/17.rs",
+    (2022, 18) => y2022_day18 in "../years/2022/bin/18.rs",
+    (2022, 19) => y2022_day19 in "../years/2022/bin/19.rs",
+    (2022, 20) => y2022_day20 in "../years/2022/bin/20.rs",
+}
+
+#[derive(Clone)]
+struct Args {
+    command: Option<String>,
+    days: Option<Vec<u8>>,
+    bench: Option<BenchOptions>,
+    threshold: f64,
+    year: Option<u16>,
+    /// The arguments after the command that are not read here, including flags read elsewhere.
+    free: Vec<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = pico_args::Arguments::from_env();
+    let mut parse = || -> Result<Args, pico_args::Error> {
+        Ok(Args {
+            command: args.subcommand()?,
+            days: args.opt_value_from_fn("--days", parse_days)?,
+            bench: BenchOptions::from_args()?,
+            threshold: args
+                .opt_value_from_str("--threshold")?
+                .unwrap_or(history::DEFAULT_THRESHOLD),
+            year: args.opt_value_from_str(["-y", "--year"])?,
+            free: vec![],
+        })
+    };
+    let mut parsed = parse().map_err(|e| e.to_string())?;
+    parsed.free = args
+        .finish()
+        .into_iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    Ok(parsed)
+}
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
+/// The solutions of `--year` (or the default year), filtered by `--days`.
+fn selected_solutions(args: &Args) -> Result<Vec<Solution>, String> {
+    let year = config::resolve_year(args.year)?;
+    Ok(solutions()
+        .into_iter()
+        .filter(|solution| solution.year == year)
+        .filter(|solution| match &args.days {
+            Some(days) => days.contains(&solution.day),
+            None => true,
+        })
+        .collect())
+}
 
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+fn run_day(solution: &Solution, bench_options: Option<&BenchOptions>) -> [PartResult; 2] {
+    let mut results = match load_input(solution.year, solution.day) {
+        Ok(input) => {
+            let mut results = solution.run(&input);
+            if let Some(options) = bench_options {
+                for result in results.iter_mut().filter(|result| result.is_solved()) {
+                    result.bench = Some(bench(solution.part(result.part), &input, options));
                 }
+            }
+            results
+        }
+        Err(error) => [
+            PartResult::failed(solution.day, 1, &error),
+            PartResult::failed(solution.day, 2, error),
+        ],
+    };
+
+    for result in results.iter_mut() {
+        if let Err(e) = result.verify(solution.year) {
+            eprintln!("Failed to read answers: {}", e);
+            break;
+        }
+    }
+
+    results
+}
+
+/// Appends benchmarked parts to the history file and reports parts that got slower.
+fn record_history(year: u16, results: &[PartResult], threshold: f64) {
+    let path = history::default_path();
+    let previous = match history::read(&path) {
+        Ok(previous) => previous,
+        Err(e) => {
+            eprintln!(
+                "Failed to read benchmark history \"{}\": {}",
+                path.display(),
+                e
             );
+            return;
+        }
+    };
+
+    let entries = history::entries(year, results, &history::current_commit());
+    let regressions = history::find_regressions(&previous, &entries, threshold);
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+    for regression in &regressions {
+        println!(
+            "⚠️  Day {:02} part {} got {:.1}% slower: {:.2?} → {:.2?} (last recorded at {}).",
+            regression.day,
+            regression.part,
+            regression.slowdown,
+            regression.previous.stats.median,
+            regression.current.median,
+            regression.previous.commit
+        );
+    }
+
+    if let Err(e) = history::append(&path, &entries) {
+        eprintln!(
+            "Failed to write benchmark history \"{}\": {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+/// `cargo all`: runs every selected day and prints the results.
+fn run_all(args: &Args) -> Result<(), String> {
+    let format = OutputFormat::from_args();
+    let year = config::resolve_year(args.year)?;
+
+    let results: Vec<PartResult> = selected_solutions(args)?
+        .iter()
+        .flat_map(|solution| {
+            if format == OutputFormat::Human {
+                println!("----------");
+                println!(
+                    "{}| Day {:02} |{}",
+                    ansi(ANSI_BOLD),
+                    solution.day,
+                    ansi(ANSI_RESET)
+                );
+                println!("----------");
             }
+
+            let results = run_day(solution, args.bench.as_ref());
+            for result in &results {
+                result.print(format);
+            }
+            results
         })
-        .sum();
+        .collect();
+
+    if format == OutputFormat::Human && args.bench.is_some() {
+        println!("----------");
+        print_table(&results);
+        println!();
+    }
+
+    if args.bench.is_some() {
+        record_history(year, &results, args.threshold);
+    }
+
+    if format == OutputFormat::Human {
+        let total: Duration = results
+            .iter()
+            .filter(|result| result.is_solved())
+            .map(|result| result.bench.map_or(result.elapsed, |stats| stats.median))
+            .sum();
+
+        println!(
+            "{}Total:{} {}{:.2?}{}",
+            ansi(ANSI_BOLD),
+            ansi(ANSI_RESET),
+            ansi(ANSI_ITALIC),
+            total,
+            ansi(ANSI_RESET)
+        );
+    }
+
+    if results.iter().any(|result| result.is_failed()) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// `cargo solve <day>`: runs a single day of `--year` (or the default year).
+fn solve(args: &Args) -> Result<(), String> {
+    let day = match positional_args(&args.free).first() {
+        Some(day) => parse_day(day)?,
+        None => return Err("usage: cargo solve <day>".into()),
+    };
+
+    let year = config::resolve_year(args.year)?;
+    if !solutions()
+        .iter()
+        .any(|solution| solution.year == year && solution.day == day)
+    {
+        return Err(format!(
+            "{} day {:02} is not registered in src/main.rs.",
+            year, day
+        ));
+    }
+
+    run_all(&Args {
+        days: Some(vec![day]),
+        ..args.clone()
+    })
+}
+
+/// `cargo readme`: rewrites the results table in `README.md` from local runs.
+/// Timings come from `--bench` if passed, otherwise from the latest benchmark history.
+fn update_readme(args: &Args) -> Result<(), String> {
+    let path = advent_of_code::project_root().join("README.md");
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("could not read \"{}\": {}", path.display(), e))?;
 
+    let year = config::resolve_year(args.year)?;
+
+    let solutions = selected_solutions(args)?;
+    let mut results: Vec<PartResult> = solutions
+        .iter()
+        .flat_map(|solution| run_day(solution, args.bench.as_ref()))
+        .collect();
+
+    if args.bench.is_none() {
+        let recorded = history::read(&history::default_path()).unwrap_or_default();
+        for result in results.iter_mut().filter(|result| result.is_solved()) {
+            result.bench =
+                history::latest(&recorded, year, result.day, result.part).map(|entry| entry.stats);
+        }
+    }
+
+    let days: Vec<u8> = solutions.iter().map(|solution| solution.day).collect();
+    let table = readme::render_table(year, &days, &results);
+    let updated = readme::replace_table(&contents, &table).ok_or(format!(
+        "README.md has no \"{}\" marker.",
+        readme::TABLE_START
+    ))?;
+
+    fs::write(&path, updated)
+        .map_err(|e| format!("could not write \"{}\": {}", path.display(), e))?;
+
+    let solved = results.iter().filter(|result| result.is_solved()).count();
+    println!(
+        "🎄 Updated results table in \"{}\" ({} parts solved).",
+        path.display(),
+        solved
+    );
+    Ok(())
+}
+
+/// `cargo verify`: checks every day that has an answers file. Returns `false` on any mismatch.
+fn verify(args: &Args) -> Result<bool, String> {
+    let mut checked = 0;
+    let mut failures = 0;
+
+    for solution in selected_solutions(args)? {
+        match answers::read(solution.year, solution.day) {
+            Ok(Some(_)) => {}
+            Ok(None) => continue,
+            Err(e) => {
+                println!("❌ Day {:02}: {}", solution.day, e);
+                failures += 1;
+                continue;
+            }
+        }
+
+        for result in run_day(&solution, None) {
+            let got = match &result.outcome {
+                Outcome::Solved(answer) => answer.clone(),
+                Outcome::Unsolved => "not solved".into(),
+                Outcome::Failed(error) => format!("failed: {}", error),
+            };
+
+            match &result.verdict {
+                Some(Verdict::Correct) => {
+                    println!("✅ Day {:02} part {}: {}", result.day, result.part, got);
+                }
+                Some(Verdict::Incorrect { expected }) => {
+                    println!(
+                        "❌ Day {:02} part {}: expected {}, got {}",
+                        result.day, result.part, expected, got
+                    );
+                    failures += 1;
+                }
+                None => continue,
+            }
+            checked += 1;
+        }
+    }
+
+    println!("---");
     println!(
-        "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
+        "🎄 Checked {} parts against stored answers, {} failed.",
+        checked, failures
     );
+    Ok(failures == 0)
+}
+
+/// `cargo submit <day> <part>`: runs a part and submits its answer, unless the guess log already
+/// rules it out. Returns `false` unless the answer was accepted.
+fn submit(args: &Args) -> Result<bool, String> {
+    let (day, part) = match positional_args(&args.free).as_slice() {
+        [day, part] => (parse_day(day).ok(), part.parse::<u8>().ok()),
+        _ => (None, None),
+    };
+    let (Some(day), Some(part @ 1..=2)) = (day, part) else {
+        return Err("usage: cargo submit <day> <1|2>".into());
+    };
+
+    let year = config::resolve_year(args.year)?;
+    let solution = solutions()
+        .into_iter()
+        .find(|solution| solution.year == year && solution.day == day)
+        .ok_or(format!(
+            "{} day {:02} is not registered in src/main.rs.",
+            year, day
+        ))?;
+    let input = load_input(year, day)?;
+
+    let result = run_part(day, part, solution.part(part), &input);
+    result.print(OutputFormat::Human);
+    let answer = match result.outcome {
+        Outcome::Solved(answer) => answer,
+        Outcome::Unsolved => return Err(format!("part {} is not solved yet.", part)),
+        Outcome::Failed(error) => return Err(format!("part {} failed: {}", part, error)),
+    };
+
+    let log_path = submit::log_path(year, day);
+    let log = submit::read_log(&log_path)
+        .map_err(|e| format!("could not read \"{}\": {}", log_path.display(), e))?;
+    if let Err(refusal) = submit::check(&log, part, &answer, submit::now()) {
+        return Err(format!("not submitting {}: {}", answer, refusal));
+    }
+
+    let client = Client::from_env().map_err(|e| e.to_string())?;
+    let reply = client
+        .submit(year, day, part, &answer)
+        .map_err(|e| e.to_string())?;
+    let response = submit::parse_response(&reply);
+
+    submit::append(&log_path, &Guess::new(part, &answer, &response))
+        .map_err(|e| format!("could not write \"{}\": {}", log_path.display(), e))?;
+
+    println!("---");
+    println!("{}", reply.trim());
+
+    if response != Response::Correct {
+        return Ok(false);
+    }
+    answers::record(year, day, part, &answer)?;
+    println!(
+        "🎄 Stored the answer in \"{}\".",
+        answers::answers_path(year, day).display()
+    );
+    Ok(true)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let (result, action) = match args.command.as_deref() {
+        None => (run_all(&args).map(|_| true), "run solutions"),
+        Some("solve") => (solve(&args).map(|_| true), "run solution"),
+        Some("readme") => (update_readme(&args).map(|_| true), "update README"),
+        Some("verify") => (verify(&args), "verify answers"),
+        Some("submit") => (submit(&args), "submit"),
+        Some(command) => {
+            eprintln!(
+                "Unknown command \"{}\". Available commands: solve, readme, verify, submit",
+                command
+            );
+            process::exit(1);
+        }
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Failed to {}: {}", action, e);
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,146 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Per-day puzzle constants that differ between the examples and the real input.
+//!
+//! ```ignore
+//! advent_of_code::params! {
+//!     pub struct Params {
+//!         row: i32 = 2000000,
+//!     }
+//! }
+//!
+//! pub fn part_one(input: &str, params: &Params) -> Option<u32> { .. }
+//!
+//! fn main() {
+//!     advent_of_code::solve!(1, part_one, params: Params);
+//! }
+//! ```
+//!
+//! The defaults are the values for the real input. They can be overridden on the command line
+//! (`cargo solve 15 -- --row 10`, underscores in names become dashes) and from an example's
+//! `.toml` (`row = 10`).
+
+/// Implemented by the struct generated with [`params!`](crate::params!).
+pub trait Params: Default {
+    /// Field names, in declaration order.
+    const KEYS: &'static [&'static str];
+
+    /// Parses `value` into the field named `key`.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// The defaults with every pair applied. Unknown keys are an error.
+    fn from_pairs<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Result<Self, String> {
+        let mut params = Self::default();
+        for (key, value) in pairs {
+            params.set(key, value)?;
+        }
+        Ok(params)
+    }
+
+    /// The defaults with any `--key value` or `--key=value` flags from the command line applied.
+    fn from_args() -> Result<Self, String> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        Self::from_pairs(flag_values(&args, Self::KEYS))
+    }
+}
+
+/// Finds `--key value` and `--key=value` flags for the given keys. Underscores in keys are
+/// matched as dashes.
+fn flag_values<'a>(args: &'a [String], keys: &'a [&'a str]) -> Vec<(&'a str, &'a str)> {
+    let mut pairs = vec![];
+
+    for key in keys {
+        let flag = format!("--{}", key.replace('_', "-"));
+        for (index, arg) in args.iter().enumerate() {
+            if *arg == flag {
+                if let Some(value) = args.get(index + 1) {
+                    pairs.push((*key, value.as_str()));
+                }
+            } else if let Some(value) = arg.strip_prefix(&flag).and_then(|v| v.strip_prefix('=')) {
+                pairs.push((*key, value));
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Declares a day's parameters struct with defaults and implements [`Params`] for it.
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident : $ty:ty = $default:expr),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        $vis struct $name {
+            $($(#[$field_meta])* pub $field: $ty),*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default),*
+                }
+            }
+        }
+
+        impl advent_of_code::params::Params for $name {
+            const KEYS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(stringify!($field) => {
+                        self.$field = value.parse().map_err(|_| {
+                            format!("invalid value \"{}\" for parameter \"{}\".", value, key)
+                        })?;
+                    })*
+                    _ => return Err(format!("unknown parameter \"{}\".", key)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    // the macro refers to the crate by name, as it does in the solution binaries.
+    use crate as advent_of_code;
+
+    advent_of_code::params! {
+        struct TestParams {
+            row: i32 = 2000000,
+            upper_bound: u64 = 4000000,
+        }
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_pairs() {
+        let params = TestParams::from_pairs([("row", "10")]).unwrap();
+        assert_eq!(params.row, 10);
+        assert_eq!(params.upper_bound, 4000000);
+
+        assert!(TestParams::from_pairs([("row", "ten")]).is_err());
+        assert!(TestParams::from_pairs([("column", "1")]).is_err());
+    }
+
+    #[test]
+    fn test_flag_values() {
+        let args = args(&["--json", "--row", "10", "--upper-bound=20", "--bench"]);
+        assert_eq!(
+            flag_values(&args, TestParams::KEYS),
+            vec![("row", "10"), ("upper_bound", "20")]
+        );
+    }
+}
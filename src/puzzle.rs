@@ -0,0 +1,308 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Reads the worked example out of a downloaded puzzle description (`years/<year>/puzzles/NN.md`)
+//! and stores it as the day's `example` (see [`crate::examples`]).
+//!
+//! The example input is the code block of part one introduced with "For example", or else its
+//! first code block. The expected answer of a part is the last emphasised code span
+//! (`` `*24000*` ``, `` *`24000`* `` or `**24000**`) in its text, preferring spans outside of list
+//! items, which usually walk through intermediate results. This matches most puzzles, but not
+//! all: check the generated `example.toml` when a day's example tests fail.
+use crate::examples::examples_dir;
+use crate::toml;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleExample {
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+/// What [`update_example`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Updated {
+    pub input: Option<PathBuf>,
+    /// The expected answers that were added, by part.
+    pub answers: Vec<(u8, String)>,
+}
+
+impl Updated {
+    pub fn print(&self) {
+        if let Some(path) = &self.input {
+            println!("🎄 Wrote the puzzle's example to \"{}\".", path.display());
+        }
+        for (part, answer) in &self.answers {
+            println!("🎄 Expecting {} for part {} of the example.", answer, part);
+        }
+    }
+}
+
+/// The contents of every fenced code block, in order.
+pub fn code_blocks(markdown: &str) -> Vec<String> {
+    introduced_code_blocks(markdown)
+        .into_iter()
+        .map(|(_, block)| block)
+        .collect()
+}
+
+/// Every fenced code block with the last line of text before it.
+fn introduced_code_blocks(markdown: &str) -> Vec<(&str, String)> {
+    let mut blocks = vec![];
+    let mut intro = "";
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        match (line.starts_with("```"), current.as_mut()) {
+            (true, None) => current = Some(String::new()),
+            // `<pre>` blocks end with a newline of their own.
+            (true, Some(block)) => {
+                blocks.push((intro, block.trim_end_matches('\n').to_string() + "\n"));
+                current = None;
+            }
+            (false, Some(block)) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            (false, None) if !line.trim().is_empty() => intro = line,
+            (false, None) => {}
+        }
+    }
+
+    blocks
+}
+
+/// The example input of part one: the first code block introduced with "For example" or "...
+/// example:", falling back to the first code block.
+fn example_input(part_one: &str) -> Option<String> {
+    let blocks = introduced_code_blocks(part_one);
+    let introduced = blocks.iter().position(|(intro, _)| {
+        let intro = intro.to_lowercase();
+        intro.contains("for example") || intro.trim_end().ends_with("example:")
+    });
+
+    let index = introduced.unwrap_or(0);
+    blocks.into_iter().nth(index).map(|(_, block)| block)
+}
+
+/// Splits a description into the text of part one and, once unlocked, part two. The answers
+/// given for the real input are cut off.
+fn parts(markdown: &str) -> (String, Option<String>) {
+    let mut parts = vec![String::new()];
+    let mut answered = false;
+
+    for line in markdown.lines() {
+        if line.contains("--- Part Two ---") {
+            parts.push(String::new());
+            answered = false;
+        } else if line.starts_with("Your puzzle answer was") {
+            answered = true;
+        } else if !answered {
+            let part = parts.last_mut().unwrap();
+            part.push_str(line);
+            part.push('\n');
+        }
+    }
+
+    let mut parts = parts.into_iter();
+    (parts.next().unwrap(), parts.next())
+}
+
+/// The emphasised code spans on a line.
+fn emphasised(line: &str) -> Vec<String> {
+    const MARKERS: [(&str, &str); 3] = [("`*", "*`"), ("*`", "`*"), ("**", "**")];
+
+    let mut spans = vec![];
+    let mut rest = line;
+
+    loop {
+        let next = MARKERS
+            .iter()
+            .filter_map(|(open, close)| Some((rest.find(open)?, *open, *close)))
+            .min_by_key(|(index, ..)| *index);
+        let Some((index, open, close)) = next else {
+            break;
+        };
+
+        let after = &rest[index + open.len()..];
+        match after.find(close) {
+            Some(end) if end > 0 => {
+                spans.push(after[..end].to_string());
+                rest = &after[end + close.len()..];
+            }
+            _ => rest = after,
+        }
+    }
+
+    spans
+}
+
+/// The expected answer in the text of a part.
+fn answer(text: &str) -> Option<String> {
+    let mut in_code = false;
+    let mut last = None;
+    let mut last_outside_list = None;
+
+    for line in text.lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        if let Some(span) = emphasised(line).pop() {
+            if !line.starts_with("* ") {
+                last_outside_list = Some(span.clone());
+            }
+            last = Some(span);
+        }
+    }
+
+    last_outside_list.or(last)
+}
+
+/// The example of a puzzle description. `None` if part one has no code block.
+pub fn example(markdown: &str) -> Option<PuzzleExample> {
+    let (part_one, part_two) = parts(markdown);
+    let input = example_input(&part_one)?;
+
+    Some(PuzzleExample {
+        input,
+        part_one: answer(&part_one),
+        part_two: part_two.as_deref().and_then(answer),
+    })
+}
+
+fn toml_value(value: &str) -> String {
+    let numeric = value.strip_prefix('-').unwrap_or(value);
+    if !numeric.is_empty() && numeric.chars().all(|c| c.is_ascii_digit()) {
+        value.into()
+    } else {
+        toml::quote(value)
+    }
+}
+
+/// Stores the example of the day's downloaded puzzle as `examples/NN/example.txt` and
+/// `example.toml`. Existing input and answers are kept unless `overwrite` is set, so running
+/// `cargo download --refresh` once part two is unlocked, which fetches the description again,
+/// only adds the new answer. Does nothing if the puzzle has not been downloaded.
+pub fn update_example(year: u16, day: u8, overwrite: bool) -> Result<Updated, String> {
+    let path = crate::client::puzzle_path(year, day);
+    let markdown = match fs::read_to_string(&path) {
+        Ok(markdown) => markdown,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Updated::default()),
+        Err(e) => return Err(format!("could not read \"{}\": {}", path.display(), e)),
+    };
+    let Some(example) = example(&markdown) else {
+        return Ok(Updated::default());
+    };
+
+    let dir = examples_dir(year, day);
+    let input_path = dir.join("example.txt");
+    let meta_path = dir.join("example.toml");
+    let io_error = |path: &PathBuf, e: io::Error| format!("\"{}\": {}", path.display(), e);
+
+    fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+    let mut updated = Updated::default();
+
+    let has_input = fs::metadata(&input_path).is_ok_and(|meta| meta.len() > 0);
+    if overwrite || !has_input {
+        fs::write(&input_path, &example.input).map_err(|e| io_error(&input_path, e))?;
+        updated.input = Some(input_path);
+    }
+
+    let mut contents = match fs::read_to_string(&meta_path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(io_error(&meta_path, e)),
+    };
+    let pairs = toml::parse(&contents).map_err(|e| format!("{}: {}", meta_path.display(), e))?;
+
+    for (part, answer) in [(1, example.part_one), (2, example.part_two)] {
+        let Some(answer) = answer else {
+            continue;
+        };
+        let key = format!("part{}", part);
+        if pairs.iter().any(|(k, _)| *k == key) {
+            if !overwrite {
+                continue;
+            }
+            contents = contents
+                .lines()
+                .filter(|line| line.split('=').next().map(str::trim) != Some(key.as_str()))
+                .map(|line| format!("{}\n", line))
+                .collect();
+        }
+
+        contents.push_str(&format!("{} = {}\n", key, toml_value(&answer)));
+        updated.answers.push((part, answer));
+    }
+
+    if !updated.answers.is_empty() {
+        fs::write(&meta_path, contents).map_err(|e| io_error(&meta_path, e))?;
+    }
+
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "\\--- Day 1: Test ---\n----------\n\nThe shapes:\n\n```\n####\n```\n\n\
+        For example:\n\n```\n1000\n\n\
+        2000\n\n```\n\n* The first Elf has `*1000*`.\n* The second Elf has `*2000*`.\n\n\
+        In the example above, this is *`2000`*.\n\nYour puzzle answer was `69177`.\n\n\
+        \\--- Part Two ---\n----------\n\nThe sum is **3000**.\n\n\
+        Your puzzle answer was `207456`.\n";
+
+    #[test]
+    fn test_code_blocks() {
+        let markdown = "For example:\n\n```\n1000\n\n2000\n\n```\n\nThen:\n\n```\n3000\n```\n";
+        assert_eq!(code_blocks(markdown), vec!["1000\n\n2000\n", "3000\n"]);
+        assert!(code_blocks("```\nunterminated\n").is_empty());
+    }
+
+    #[test]
+    fn test_emphasised() {
+        assert_eq!(
+            emphasised("a `*1*` b *`2`* c **3** d *word* `code` e"),
+            vec!["1", "2", "3"]
+        );
+        assert!(emphasised("* list item with *emphasis*").is_empty());
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(
+            example(PUZZLE),
+            Some(PuzzleExample {
+                input: "1000\n\n2000\n".into(),
+                part_one: Some("2000".into()),
+                part_two: Some("3000".into()),
+            })
+        );
+
+        let locked = PUZZLE.split("\\--- Part Two").next().unwrap();
+        assert_eq!(example(locked).unwrap().part_two, None);
+        assert_eq!(example("no example"), None);
+    }
+
+    #[test]
+    fn test_answers_in_downloaded_puzzles() {
+        for (day, part_one, part_two) in
+            [(1, "24000", "45000"), (3, "157", "70"), (5, "CMZ", "MCD")]
+        {
+            let markdown = fs::read_to_string(crate::client::puzzle_path(2022, day)).unwrap();
+            let example = example(&markdown).unwrap();
+            assert_eq!(example.part_one.as_deref(), Some(part_one), "day {}", day);
+            assert_eq!(example.part_two.as_deref(), Some(part_two), "day {}", day);
+        }
+    }
+}
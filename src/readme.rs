@@ -0,0 +1,167 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Renders the results table in `README.md` from local solution runs.
+use crate::runner::PartResult;
+use std::time::Duration;
+
+pub const TABLE_START: &str = "<!--- advent_readme_stars table --->";
+pub const TABLE_END: &str = "<!--- advent_readme_stars table end --->";
+
+const SOLVED: &str = "⭐";
+const UNSOLVED: &str = "🕓";
+
+/// The timing shown for a part: its benchmark median. Parts without benchmark data get no
+/// timing, as a single run is too noisy to publish.
+fn timing(result: &PartResult) -> Option<Duration> {
+    result
+        .bench
+        .filter(|_| result.is_solved())
+        .map(|stats| stats.median)
+}
+
+/// Renders the heading and table for the given days. `results` holds both parts of every day.
+pub fn render_table(year: u16, days: &[u8], results: &[PartResult]) -> String {
+    let mut table = format!("## {} Results\n\n", year);
+    table.push_str("| Day | Part 1 | Part 2 | Time (Part 1) | Time (Part 2) |\n");
+    table.push_str("| :---: | :---: | :---: | ---: | ---: |\n");
+
+    for &day in days {
+        let find = |part: u8| results.iter().find(|r| r.day == day && r.part == part);
+        let star = |part: u8| match find(part) {
+            Some(result) if result.is_solved() => SOLVED,
+            _ => UNSOLVED,
+        };
+        let time = |part: u8| match find(part).and_then(timing) {
+            Some(elapsed) => format!("{:.2?}", elapsed),
+            None => String::new(),
+        };
+
+        table.push_str(&format!(
+            "| [Day {}](https://adventofcode.com/{}/day/{}) | {} | {} | {} | {} |\n",
+            day,
+            year,
+            day,
+            star(1),
+            star(2),
+            time(1),
+            time(2)
+        ));
+    }
+
+    table
+}
+
+/// Replaces everything between the table markers with `table`.
+/// If only the start marker exists, the heading and table directly after it are replaced and
+/// an end marker is added. Returns `None` if the README has no start marker.
+pub fn replace_table(readme: &str, table: &str) -> Option<String> {
+    let start = readme.find(TABLE_START)? + TABLE_START.len();
+    let rest = &readme[start..];
+
+    let end = match rest.find(TABLE_END) {
+        Some(end) => start + end + TABLE_END.len(),
+        None => {
+            let mut offset = 0;
+            for line in rest.split_inclusive('\n') {
+                let trimmed = line.trim();
+                let in_section =
+                    trimmed.is_empty() || trimmed.starts_with("## ") || trimmed.starts_with('|');
+                if offset > 0 && !in_section {
+                    break;
+                }
+                offset += line.len();
+            }
+            start + offset
+        }
+    };
+
+    let tail = readme[end..].trim_start_matches('\n');
+    let separator = if tail.is_empty() { "" } else { "\n" };
+
+    Some(format!(
+        "{}\n{}{}\n{}{}",
+        &readme[..start],
+        table,
+        TABLE_END,
+        separator,
+        tail
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::BenchStats;
+    use crate::runner::Outcome;
+
+    fn result(day: u8, part: u8, outcome: Outcome) -> PartResult {
+        PartResult {
+            day,
+            part,
+            outcome,
+            elapsed: Duration::from_micros(1500),
+            bench: Some(BenchStats {
+                runs: 10,
+                min: Duration::from_micros(900),
+                median: Duration::from_micros(1000),
+                mean: Duration::from_micros(1100),
+                stddev: Duration::from_micros(50),
+            }),
+            verdict: None,
+        }
+    }
+
+    #[test]
+    fn test_render_table() {
+        let results = vec![
+            result(1, 1, Outcome::Solved("24000".into())),
+            PartResult {
+                bench: None,
+                ..result(1, 2, Outcome::Solved("45000".into()))
+            },
+            result(19, 1, Outcome::Unsolved),
+            result(19, 2, Outcome::Failed("panicked.".into())),
+        ];
+        let table = render_table(2022, &[1, 19], &results);
+
+        assert!(table.starts_with("## 2022 Results\n"));
+        assert!(table
+            .contains("| [Day 1](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ | 1.00ms |  |\n"));
+        assert!(
+            table.contains("| [Day 19](https://adventofcode.com/2022/day/19) | 🕓 | 🕓 |  |  |\n")
+        );
+    }
+
+    #[test]
+    fn test_replace_table_without_end_marker() {
+        let readme = format!(
+            "# Title\n\n{}\n## 2022 Results\n\n| Day |\n| :---: |\n| old |\n\nFooter text.\n",
+            TABLE_START
+        );
+        let replaced = replace_table(&readme, "## 2022 Results\n\n| new |\n").unwrap();
+
+        assert_eq!(
+            replaced,
+            format!(
+                "# Title\n\n{}\n## 2022 Results\n\n| new |\n{}\n\nFooter text.\n",
+                TABLE_START, TABLE_END
+            )
+        );
+        assert_eq!(
+            replace_table(&replaced, "## 2022 Results\n\n| new |\n"),
+            Some(replaced)
+        );
+    }
+
+    #[test]
+    fn test_replace_table_at_end_of_file() {
+        let readme = format!("{}\n## 2022 Results\n\n| old |\n", TABLE_START);
+        assert_eq!(
+            replace_table(&readme, "| new |\n"),
+            Some(format!("{}\n| new |\n{}\n", TABLE_START, TABLE_END))
+        );
+        assert_eq!(replace_table("# No markers", "| new |\n"), None);
+    }
+}
@@ -0,0 +1,249 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Renders a downloaded puzzle description for the terminal: headings and emphasis in bold, code
+//! blocks indented and left as they are, links reduced to their text and everything else wrapped
+//! to the terminal width. Without color, emphasis keeps its `*` markers so answers stay visible.
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::env;
+
+const DEFAULT_WIDTH: usize = 80;
+const CODE_INDENT: &str = "    ";
+
+/// The width to wrap at: `$COLUMNS` if set, otherwise 80 columns.
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|columns| *columns > 20)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+pub fn render(markdown: &str, width: usize, color: bool) -> String {
+    let mut out = String::new();
+    let mut lines = markdown.lines().peekable();
+    let mut in_code = false;
+
+    while let Some(line) = lines.next() {
+        if line.starts_with("```") {
+            if in_code {
+                // `<pre>` blocks end with a newline of their own.
+                while out.ends_with("\n\n") {
+                    out.pop();
+                }
+            }
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            if !line.is_empty() {
+                out.push_str(CODE_INDENT);
+                out.push_str(line);
+            }
+            out.push('\n');
+            continue;
+        }
+
+        let heading = line.trim_start_matches('\\');
+        if heading.starts_with("---") && heading.ends_with("---") {
+            out.push_str(&style(heading, ANSI_BOLD, color));
+            out.push('\n');
+            if lines.peek().is_some_and(|next| is_rule(next)) {
+                lines.next();
+            }
+        } else if let Some(item) = line.strip_prefix("* ") {
+            out.push_str(&wrap(&inline(item, color), width, "  - ", "    "));
+        } else if line.trim().is_empty() {
+            if !out.is_empty() && !out.ends_with("\n\n") {
+                out.push('\n');
+            }
+        } else {
+            out.push_str(&wrap(&inline(line, color), width, "", ""));
+        }
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+fn is_rule(line: &str) -> bool {
+    !line.is_empty() && line.chars().all(|c| c == '-')
+}
+
+fn style(text: &str, code: &str, color: bool) -> String {
+    if color {
+        format!("{}{}{}", code, text, ANSI_RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+fn emphasis(text: &str, color: bool) -> String {
+    if color {
+        style(text, ANSI_BOLD, true)
+    } else {
+        format!("*{}*", text)
+    }
+}
+
+/// Renders the inline markup of a line: `\` escapes, `*emphasis*`, `` `code` ``, emphasised code
+/// in either nesting and `[links](href)`.
+fn inline(text: &str, color: bool) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+
+        let (rendered, remaining) = match c {
+            '\\' if !after.is_empty() => {
+                let escaped = after.chars().next().unwrap();
+                (escaped.to_string(), &after[escaped.len_utf8()..])
+            }
+            '`' => match after.find('`') {
+                Some(end) => {
+                    let code = &after[..end];
+                    let rendered = match code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+                        Some(em) if !em.is_empty() => emphasis(em, color),
+                        _ => style(code, ANSI_ITALIC, color),
+                    };
+                    (rendered, &after[end + 1..])
+                }
+                None => (c.to_string(), after),
+            },
+            '*' => {
+                let marker = if after.starts_with('*') { "**" } else { "*" };
+                let inner = &rest[marker.len()..];
+                match inner.find(marker) {
+                    Some(end) if end > 0 && !inner.starts_with(' ') => (
+                        emphasis(&inline(&inner[..end], false), color),
+                        &inner[end + marker.len()..],
+                    ),
+                    _ => (c.to_string(), after),
+                }
+            }
+            '[' => match link(after) {
+                Some((label, remaining)) => (inline(label, color), remaining),
+                None => (c.to_string(), after),
+            },
+            _ => (c.to_string(), after),
+        };
+
+        out.push_str(&rendered);
+        rest = remaining;
+    }
+
+    out
+}
+
+/// Splits `text](href) rest` into the link text and the rest.
+fn link(after_bracket: &str) -> Option<(&str, &str)> {
+    let close = after_bracket.find("](")?;
+    let label = &after_bracket[..close];
+    if label.contains('[') {
+        return None;
+    }
+    let href = &after_bracket[close + 2..];
+    let mut depth = 0;
+    let end = href.find(|c| {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return true,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        false
+    })?;
+    Some((label, &href[end + 1..]))
+}
+
+/// The number of columns `text` takes up, ignoring ANSI escape codes.
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+/// Wraps `text` at word boundaries. The first line starts with `first`, the others with `rest`.
+fn wrap(text: &str, width: usize, first: &str, rest: &str) -> String {
+    let mut out = String::from(first);
+    let mut column = visible_width(first);
+    let mut line_empty = true;
+
+    for word in text.split(' ').filter(|word| !word.is_empty()) {
+        let word_width = visible_width(word);
+        if !line_empty && column + 1 + word_width > width {
+            out.push('\n');
+            out.push_str(rest);
+            column = visible_width(rest);
+            line_empty = true;
+        }
+        if !line_empty {
+            out.push(' ');
+            column += 1;
+        }
+        out.push_str(word);
+        column += word_width;
+        line_empty = false;
+    }
+
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline() {
+        assert_eq!(
+            inline("a *star* with `code`, `*24000*` and *`45000`*", false),
+            "a *star* with code, *24000* and *45000*"
+        );
+        assert_eq!(
+            inline(
+                "see [the calendar](/2022), [x](javascript:f(0);) or \\*\\* and 2 * 3",
+                false
+            ),
+            "see the calendar, x or ** and 2 * 3"
+        );
+        assert_eq!(
+            inline("is `*7*`", true),
+            format!("is {}7{}", ANSI_BOLD, ANSI_RESET)
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("one two three four", 9, "- ", "  "),
+            "- one two\n  three\n  four\n"
+        );
+        let bold = format!("{}three{}", ANSI_BOLD, ANSI_RESET);
+        assert_eq!(
+            wrap(&format!("one two {}", bold), 13, "", ""),
+            format!("one two {}\n", bold)
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let markdown =
+            "\\--- Day 1: Test ---\n----------\n\nFor example:\n\n```\n1000  2000\n\n3000\n\n```\n\n\
+                        * The first Elf has `*6000*` Calories.\n\n\n\nThe end.\n";
+        assert_eq!(
+            render(markdown, 80, false),
+            "--- Day 1: Test ---\n\nFor example:\n\n    1000  2000\n\n    3000\n\n  - The first Elf has *6000* \
+             Calories.\n\nThe end.\n"
+        );
+    }
+}
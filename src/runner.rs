@@ -0,0 +1,535 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::answers::{self, Verdict};
+use crate::bench::{self, BenchOptions, BenchStats};
+use crate::config;
+use crate::json;
+use crate::try_read_file;
+use crate::{ansi, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::any::Any;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Flag passed to a solution binary to switch its output to JSON lines.
+pub const JSON_FLAG: &str = "--json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+impl OutputFormat {
+    /// JSON if `--json` was passed, otherwise the format set in the config.
+    pub fn from_args() -> Self {
+        if std::env::args().skip(1).any(|arg| arg == JSON_FLAG) {
+            OutputFormat::Json
+        } else {
+            config::get().format
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The answer rendered with `Display`.
+    Solved(String),
+    /// The part returned `None`.
+    Unsolved,
+    /// The part could not run or panicked.
+    Failed(String),
+}
+
+/// The outcome of running one part of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    /// Timing statistics, present when the part was run with `--bench`.
+    pub bench: Option<BenchStats>,
+    /// The check against `years/<year>/answers/NN.toml`, present when an answer is stored for
+    /// this part.
+    pub verdict: Option<Verdict>,
+}
+
+impl PartResult {
+    pub fn failed(day: u8, part: u8, error: impl Into<String>) -> Self {
+        PartResult {
+            day,
+            part,
+            outcome: Outcome::Failed(error.into()),
+            elapsed: Duration::ZERO,
+            bench: None,
+            verdict: None,
+        }
+    }
+
+    pub fn answer(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        matches!(self.outcome, Outcome::Solved(_))
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self.outcome, Outcome::Failed(_))
+    }
+
+    pub fn is_incorrect(&self) -> bool {
+        matches!(self.verdict, Some(Verdict::Incorrect { .. }))
+    }
+
+    /// Loads the stored answers for this day, if any, and records whether this result matches.
+    pub fn verify(&mut self, year: u16) -> Result<(), String> {
+        if let Some(answers) = answers::read(year, self.day)? {
+            self.verdict = answers.check(self);
+        }
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> String {
+        let (status, answer, error) = match &self.outcome {
+            Outcome::Solved(answer) => ("solved", json::escape(answer), "null".into()),
+            Outcome::Unsolved => ("unsolved", "null".into(), "null".into()),
+            Outcome::Failed(error) => ("failed", "null".into(), json::escape(error)),
+        };
+
+        let mut fields = vec![
+            ("day", self.day.to_string()),
+            ("part", self.part.to_string()),
+            ("status", json::escape(status)),
+            ("answer", answer),
+            ("error", error),
+            ("elapsed_ns", self.elapsed.as_nanos().to_string()),
+        ];
+
+        if let Some(verdict) = &self.verdict {
+            let (correct, expected) = match verdict {
+                Verdict::Correct => (true, self.answer().unwrap_or_default()),
+                Verdict::Incorrect { expected } => (false, expected.as_str()),
+            };
+            fields.extend([
+                ("correct", correct.to_string()),
+                ("expected", json::escape(expected)),
+            ]);
+        }
+
+        if let Some(stats) = &self.bench {
+            fields.extend([
+                ("runs", stats.runs.to_string()),
+                ("min_ns", stats.min.as_nanos().to_string()),
+                ("median_ns", stats.median.as_nanos().to_string()),
+                ("mean_ns", stats.mean.as_nanos().to_string()),
+                ("stddev_ns", stats.stddev.as_nanos().to_string()),
+            ]);
+        }
+
+        json::object(&fields)
+    }
+
+    pub fn print(&self, format: OutputFormat) {
+        match format {
+            OutputFormat::Human => {
+                println!(
+                    "🎄 {}Part {}{} 🎄",
+                    ansi(ANSI_BOLD),
+                    self.part,
+                    ansi(ANSI_RESET)
+                );
+                let mark = match &self.verdict {
+                    Some(Verdict::Correct) => " ✅".to_string(),
+                    Some(Verdict::Incorrect { expected }) => format!(" ❌ expected {}", expected),
+                    None => String::new(),
+                };
+                match &self.outcome {
+                    Outcome::Solved(answer) => println!(
+                        "{}{} {}(elapsed: {:.2?}){}",
+                        answer,
+                        mark,
+                        ansi(ANSI_ITALIC),
+                        self.elapsed,
+                        ansi(ANSI_RESET)
+                    ),
+                    Outcome::Unsolved => println!("not solved.{}", mark),
+                    Outcome::Failed(error) => println!("failed: {}{}", error, mark),
+                }
+                if let Some(stats) = &self.bench {
+                    bench::print_stats(stats);
+                }
+            }
+            OutputFormat::Json => println!("{}", self.to_json()),
+        }
+    }
+}
+
+pub type Solver = fn(&str) -> Option<String>;
+
+/// A day registered with the in-process runner.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part_one: Solver,
+    pub part_two: Solver,
+}
+
+impl Solution {
+    pub fn part(&self, part: u8) -> Solver {
+        if part == 1 {
+            self.part_one
+        } else {
+            self.part_two
+        }
+    }
+
+    pub fn run(&self, input: &str) -> [PartResult; 2] {
+        [
+            run_part(self.day, 1, self.part_one, input),
+            run_part(self.day, 2, self.part_two, input),
+        ]
+    }
+}
+
+/// Times a single part. Panics inside the part are caught and reported as [`Outcome::Failed`].
+pub fn run_part<T: Display>(
+    day: u8,
+    part: u8,
+    func: impl FnOnce(&str) -> Option<T>,
+    input: &str,
+) -> PartResult {
+    let timer = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+    let elapsed = timer.elapsed();
+
+    let outcome = match result {
+        Ok(Some(answer)) => Outcome::Solved(answer.to_string()),
+        Ok(None) => Outcome::Unsolved,
+        Err(payload) => Outcome::Failed(panic_message(payload.as_ref())),
+    };
+
+    PartResult {
+        day,
+        part,
+        outcome,
+        elapsed,
+        bench: None,
+        verdict: None,
+    }
+}
+
+/// Runs, optionally benchmarks, and prints one part. This is what `solve!` expands to.
+pub fn solve<T: Display>(
+    year: u16,
+    day: u8,
+    part: u8,
+    func: impl Fn(&str) -> Option<T>,
+    input: &str,
+) {
+    let bench_options = match BenchOptions::from_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let mut result = run_part(day, part, &func, input);
+    if let Err(e) = result.verify(year) {
+        eprintln!("Failed to read answers: {}", e);
+    }
+    if let Some(options) = bench_options {
+        if result.is_solved() {
+            result.bench = Some(bench::bench(&func, input, &options));
+        }
+    }
+
+    result.print(OutputFormat::from_args());
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panicked: {}", message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panicked: {}", message)
+    } else {
+        "panicked.".into()
+    }
+}
+
+/// Parses a day filter such as `3,7-12` into a sorted list of days.
+pub fn parse_days(value: &str) -> Result<Vec<u8>, String> {
+    let mut days = vec![];
+
+    for item in value.split(',').map(str::trim) {
+        let parse = |day: &str| parse_day(day.trim());
+
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(format!("\"{}\" is not an ascending range.", item));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse(item)?),
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+/// Parses a day between 1 and the number of days set in the config.
+pub fn parse_day(value: &str) -> Result<u8, String> {
+    let last = config::get().days;
+    match value.parse::<u8>() {
+        Ok(day) if (1..=last).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "\"{}\" is not a day between 1 and {}.",
+            value, last
+        )),
+    }
+}
+
+/// The arguments that are not flags, in order. Flags are read where they are used (`--input`,
+/// `--json`, `--bench` options and a day's params), so everything else is skipped here: a
+/// `--flag` is taken to have a value unless it is a known switch or written as `--flag=value`.
+pub fn positional_args(args: &[String]) -> Vec<&str> {
+    const SWITCHES: [&str; 2] = [JSON_FLAG, bench::BENCH_FLAG];
+
+    let mut positional = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg.as_str());
+        } else if !SWITCHES.contains(&arg.as_str()) && !arg.contains('=') {
+            args.next();
+        }
+    }
+    positional
+}
+
+/// Reads the input for `day` from `--input <path>`, `--input -` (stdin) or [`crate::input_path`].
+pub fn load_input(year: u16, day: u8) -> Result<String, String> {
+    let mut args = pico_args::Arguments::from_env();
+    let source: Option<String> = args
+        .opt_value_from_str("--input")
+        .map_err(|e| e.to_string())?;
+
+    match source.as_deref() {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("could not read input from stdin: {}", e))?;
+            Ok(input)
+        }
+        Some(path) => {
+            fs::read_to_string(path).map_err(|e| format!("could not read \"{}\": {}", path, e))
+        }
+        None => try_read_file(year, "inputs", day).map_err(|e| e.to_string()),
+    }
+}
+
+/// [`load_input`], read once per process. Prints the error and exits if the input is missing.
+pub fn input_or_exit(year: u16, day: u8) -> &'static str {
+    static INPUT: OnceLock<String> = OnceLock::new();
+
+    INPUT.get_or_init(|| match load_input(year, day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("🎄 No input for {} day {:02}: {}", year, day, e);
+            eprintln!(
+                "🎄 Run `cargo download {} --year {}`, or pass `--input <path>` or `--input -` for stdin.",
+                day, year
+            );
+            process::exit(1);
+        }
+    })
+}
+
+/// The year and day of a solution file at `years/<year>/bin/NN.rs`. Panics for other paths.
+pub fn year_and_day_from_path(path: &str) -> (u16, u8) {
+    let day = Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str()?.parse().ok());
+
+    match (crate::year_from_path(path), day) {
+        (Some(year), Some(day)) => (year, day),
+        _ => panic!("\"{}\" is not a solution at years/<year>/bin/NN.rs.", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a line written by [`PartResult::to_json`]. Returns `None` for any other line.
+    fn from_json(line: &str) -> Option<PartResult> {
+        let object = json::parse_object(line)?;
+        let elapsed_ns: u64 = object.get("elapsed_ns")?.parse_number()?;
+
+        let outcome = match object.get("status")?.as_str()? {
+            "solved" => Outcome::Solved(object.get("answer")?.as_str()?.into()),
+            "unsolved" => Outcome::Unsolved,
+            "failed" => Outcome::Failed(object.get("error")?.as_str()?.into()),
+            _ => return None,
+        };
+
+        let nanos = |key: &str| object.get(key)?.parse_number().map(Duration::from_nanos);
+        let bench = match object.get("runs") {
+            Some(runs) => Some(BenchStats {
+                runs: runs.parse_number()?,
+                min: nanos("min_ns")?,
+                median: nanos("median_ns")?,
+                mean: nanos("mean_ns")?,
+                stddev: nanos("stddev_ns")?,
+            }),
+            None => None,
+        };
+
+        let verdict = match object.get("correct") {
+            Some(correct) => Some(if correct.as_bool()? {
+                Verdict::Correct
+            } else {
+                Verdict::Incorrect {
+                    expected: object.get("expected")?.as_str()?.into(),
+                }
+            }),
+            None => None,
+        };
+
+        Some(PartResult {
+            day: object.get("day")?.parse_number()?,
+            part: object.get("part")?.parse_number()?,
+            outcome,
+            elapsed: Duration::from_nanos(elapsed_ns),
+            bench,
+            verdict,
+        })
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let solved = PartResult {
+            day: 10,
+            part: 2,
+            outcome: Outcome::Solved("##..\n#..#".into()),
+            elapsed: Duration::from_nanos(74),
+            bench: None,
+            verdict: None,
+        };
+        assert_eq!(from_json(&solved.to_json()), Some(solved));
+
+        let unsolved = PartResult {
+            day: 19,
+            part: 1,
+            outcome: Outcome::Unsolved,
+            elapsed: Duration::from_micros(755),
+            bench: Some(BenchStats {
+                runs: 3,
+                min: Duration::from_micros(700),
+                median: Duration::from_micros(755),
+                mean: Duration::from_micros(760),
+                stddev: Duration::from_nanos(52),
+            }),
+            verdict: Some(Verdict::Incorrect {
+                expected: "1234".into(),
+            }),
+        };
+        assert_eq!(from_json(&unsolved.to_json()), Some(unsolved));
+
+        let failed = PartResult::failed(3, 2, "could not open input file");
+        assert_eq!(from_json(&failed.to_json()), Some(failed));
+
+        let mut correct = run_part(1, 1, |_: &str| Some(24000), "");
+        correct.verdict = Some(Verdict::Correct);
+        assert_eq!(from_json(&correct.to_json()), Some(correct));
+    }
+
+    #[test]
+    fn test_from_json_ignores_other_output() {
+        assert_eq!(from_json("🎄 Part 1 🎄"), None);
+        assert_eq!(from_json("0 (elapsed: 10.3s)"), None);
+        assert_eq!(from_json("{\"day\":1}"), None);
+    }
+
+    #[test]
+    fn test_run_part() {
+        let result = run_part(1, 1, |input: &str| input.parse::<u32>().ok(), "42");
+        assert_eq!(result.answer(), Some("42"));
+        assert_eq!(
+            run_part(1, 2, |_: &str| None::<u32>, "").outcome,
+            Outcome::Unsolved
+        );
+    }
+
+    #[test]
+    fn test_run_part_catches_panics() {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let result = run_part(1, 1, |_: &str| -> Option<u32> { panic!("bad input") }, "");
+        panic::set_hook(hook);
+
+        assert_eq!(
+            result.outcome,
+            Outcome::Failed("panicked: bad input".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("3,7-12"), Ok(vec![3, 7, 8, 9, 10, 11, 12]));
+        assert_eq!(parse_days("5, 1-2, 2"), Ok(vec![1, 2, 5]));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("12-7").is_err());
+        assert!(parse_days("7-").is_err());
+    }
+
+    #[test]
+    fn test_positional_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            positional_args(&args(&["1", "1", "--input", "input.txt"])),
+            vec!["1", "1"]
+        );
+        assert_eq!(
+            positional_args(&args(&["--input", "-", "15", "--json", "2", "--row=10"])),
+            vec!["15", "2"]
+        );
+        assert_eq!(
+            positional_args(&args(&["15", "--row", "10", "--bench", "1"])),
+            vec!["15", "1"]
+        );
+    }
+
+    #[test]
+    fn test_year_and_day_from_path() {
+        assert_eq!(year_and_day_from_path("years/2022/bin/07.rs"), (2022, 7));
+        assert_eq!(
+            year_and_day_from_path("src/../years/2015/bin/25.rs"),
+            (2015, 25)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a solution")]
+    fn test_year_and_day_from_path_outside_years() {
+        year_and_day_from_path("src/bin/07.rs");
+    }
+}
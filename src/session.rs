@@ -0,0 +1,496 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! The session cookie used to download puzzles and submit answers, and the named profiles that
+//! `cargo aoc-session` manages for people sharing a machine.
+//!
+//! The cookie is taken from the first of:
+//!
+//! 1. the environment variable set as `session.env` in `aoc.toml` (`$AOC_SESSION`),
+//! 2. the selected profile in the profiles file (`session.profiles`), where the profile is
+//!    `$AOC_PROFILE`, else `session.profile`, else the file's active profile,
+//! 3. the plain token file set as `session.file` (`~/.adventofcode.session`, as used by aoc-cli).
+//!
+//! The profiles file looks like this and is only readable by its owner:
+//!
+//! ```toml
+//! active = "alice"
+//!
+//! [profiles]
+//! alice = "53616c7465645f5f..."
+//! bob = "53616c7465645f5f..."
+//! ```
+use crate::{config, toml};
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::hash::BuildHasher;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::SystemTime;
+
+pub const PROFILE_ENV: &str = "AOC_PROFILE";
+/// The profile `cargo aoc-session set` writes to when none is given.
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Env(String),
+    Profile { name: String, path: PathBuf },
+    File(PathBuf),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Env(name) => write!(f, "${}", name),
+            Source::Profile { name, path } => {
+                write!(f, "profile \"{}\" in \"{}\"", name, path.display())
+            }
+            Source::File(path) => write!(f, "\"{}\"", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub token: String,
+    pub source: Source,
+}
+
+impl Session {
+    pub fn masked(&self) -> String {
+        mask(&self.token)
+    }
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    Missing,
+    UnknownProfile { name: String, path: PathBuf },
+    InvalidProfiles { path: PathBuf, message: String },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::Missing => {
+                let config = config::get();
+                write!(f, "no session cookie found. Run `cargo aoc-session set`")?;
+                match &config.session_file {
+                    Some(file) => write!(
+                        f,
+                        ", set ${} or write it to {}.",
+                        config.session_env, file
+                    ),
+                    None => write!(f, " or set ${}.", config.session_env),
+                }
+            }
+            SessionError::UnknownProfile { name, path } => write!(
+                f,
+                "no session profile \"{}\" in \"{}\". Add it with `cargo aoc-session set --profile {}`.",
+                name,
+                path.display(),
+                name
+            ),
+            SessionError::InvalidProfiles { path, message } => {
+                write!(f, "{}: {}", path.display(), message)
+            }
+            SessionError::Io { path, source } => {
+                write!(f, "could not access \"{}\": {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl Error for SessionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SessionError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Shows the first and last four characters of a token.
+pub fn mask(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 12 {
+        return "*".repeat(chars.len());
+    }
+    let start: String = chars[..4].iter().collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{} ({} characters)", start, end, chars.len())
+}
+
+/// The named tokens in the profiles file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profiles {
+    pub active: Option<String>,
+    /// Tokens by profile name, in file order.
+    pub tokens: Vec<(String, String)>,
+}
+
+impl Profiles {
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut profiles = Profiles::default();
+        for (key, value) in toml::parse(contents)? {
+            match (key.as_str(), key.strip_prefix("profiles.")) {
+                ("active", _) => profiles.active = Some(value),
+                (_, Some(name)) => profiles.tokens.push((name.into(), value)),
+                _ => return Err(format!("unknown key `{}`.", key)),
+            }
+        }
+        Ok(profiles)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut out = String::from("# Written by `cargo aoc-session`.\n");
+        if let Some(active) = &self.active {
+            out.push_str(&format!("active = {}\n", toml::quote(active)));
+        }
+        out.push_str("\n[profiles]\n");
+        for (name, token) in &self.tokens {
+            out.push_str(&format!("{} = {}\n", name, toml::quote(token)));
+        }
+        out
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.tokens
+            .iter()
+            .find(|(profile, _)| profile == name)
+            .map(|(_, token)| token.as_str())
+    }
+
+    /// Adds or replaces a profile. The first profile becomes the active one.
+    pub fn set(&mut self, name: &str, token: &str) {
+        match self.tokens.iter_mut().find(|(profile, _)| profile == name) {
+            Some((_, existing)) => *existing = token.into(),
+            None => self.tokens.push((name.into(), token.into())),
+        }
+        if self.active.is_none() {
+            self.active = Some(name.into());
+        }
+    }
+
+    /// The profile to use: `$AOC_PROFILE`, else `session.profile` from the config, else the
+    /// active one.
+    pub fn selected(&self) -> Option<String> {
+        env::var(PROFILE_ENV)
+            .ok()
+            .filter(|name| !name.is_empty())
+            .or_else(|| config::get().session_profile.clone())
+            .or_else(|| self.active.clone())
+    }
+}
+
+/// Profile names are used as bare TOML keys.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn profiles_path() -> PathBuf {
+    config::resolve_path(&config::get().session_profiles)
+}
+
+/// Reads the profiles file. A missing file has no profiles.
+pub fn load_profiles() -> Result<Profiles, SessionError> {
+    let path = profiles_path();
+    match fs::read_to_string(&path) {
+        Ok(contents) => {
+            Profiles::parse(&contents).map_err(|message| SessionError::InvalidProfiles {
+                path: path.clone(),
+                message,
+            })
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Profiles::default()),
+        Err(source) => Err(SessionError::Io { path, source }),
+    }
+}
+
+pub fn save_profiles(profiles: &Profiles) -> Result<(), SessionError> {
+    let path = profiles_path();
+    write_private(&path, &profiles.to_toml()).map_err(|source| SessionError::Io { path, source })
+}
+
+/// Options for writing a file that only its owner can read.
+fn private_options() -> OpenOptions {
+    let mut options = OpenOptions::new();
+    options.write(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+}
+
+/// Writes a file that only its owner can read. A file that already exists is restricted too,
+/// before the contents are written, as the mode only applies when the file is created.
+fn write_private(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = private_options().create(true).truncate(true).open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents.as_bytes())
+}
+
+/// Writes `contents` to a new file with an unpredictable name in the temporary directory, which
+/// only its owner can read. The file is created exclusively, so a file or link that someone else
+/// placed at that path is never written through.
+fn write_temporary(prefix: &str, contents: &str) -> io::Result<PathBuf> {
+    const ATTEMPTS: u32 = 16;
+
+    for _ in 0..ATTEMPTS {
+        let suffix = RandomState::new().hash_one(SystemTime::now());
+        let path = env::temp_dir().join(format!("{}-{}-{:016x}", prefix, process::id(), suffix));
+        let mut file = match private_options().create_new(true).open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        };
+        if let Err(e) = file.write_all(contents.as_bytes()) {
+            let _ = fs::remove_file(&path);
+            return Err(e);
+        }
+        return Ok(path);
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "could not find an unused temporary file name",
+    ))
+}
+
+/// The profiles, the selected profile and the path of the profiles file.
+type LoadedProfiles = (Profiles, Option<String>, PathBuf);
+
+/// Picks the session from the sources in order of precedence. The profiles are only loaded when
+/// the environment has no token, so a broken profiles file doesn't get in the way of one.
+fn resolve_with(
+    env_token: Option<(String, String)>,
+    profiles: impl FnOnce() -> Result<LoadedProfiles, SessionError>,
+    file: Option<(PathBuf, Option<String>)>,
+) -> Result<Session, SessionError> {
+    let non_empty = |token: &str| Some(token.trim().to_string()).filter(|t| !t.is_empty());
+
+    if let Some((name, token)) = env_token {
+        if let Some(token) = non_empty(&token) {
+            return Ok(Session {
+                token,
+                source: Source::Env(name),
+            });
+        }
+    }
+
+    let (profiles, selected, path) = profiles()?;
+    if let Some(name) = selected {
+        return match profiles.get(&name).and_then(non_empty) {
+            Some(token) => Ok(Session {
+                token,
+                source: Source::Profile { name, path },
+            }),
+            None => Err(SessionError::UnknownProfile { name, path }),
+        };
+    }
+
+    match file {
+        Some((path, Some(token))) => match non_empty(&token) {
+            Some(token) => Ok(Session {
+                token,
+                source: Source::File(path),
+            }),
+            None => Err(SessionError::Missing),
+        },
+        _ => Err(SessionError::Missing),
+    }
+}
+
+/// The session cookie to use, from the sources listed in the module documentation.
+pub fn resolve() -> Result<Session, SessionError> {
+    let config = config::get();
+    let env_token = env::var(&config.session_env)
+        .ok()
+        .map(|token| (config.session_env.clone(), token));
+    let profiles = || {
+        let profiles = load_profiles()?;
+        let selected = profiles.selected();
+        Ok((profiles, selected, profiles_path()))
+    };
+    let file = config.session_file.as_deref().map(|file| {
+        let path = config::resolve_path(file);
+        let token = fs::read_to_string(&path).ok();
+        (path, token)
+    });
+
+    resolve_with(env_token, profiles, file)
+}
+
+/// A file holding the session cookie, for tools that take a path such as aoc-cli's
+/// `--session-file`. Tokens that do not come from a file are written to a temporary file that is
+/// removed again on drop.
+pub struct SessionFile {
+    pub path: PathBuf,
+    temporary: bool,
+}
+
+impl SessionFile {
+    pub fn new(session: &Session) -> Result<Self, SessionError> {
+        if let Source::File(path) = &session.source {
+            return Ok(SessionFile {
+                path: path.clone(),
+                temporary: false,
+            });
+        }
+
+        let path =
+            write_temporary("aoc-session", &session.token).map_err(|source| SessionError::Io {
+                path: env::temp_dir(),
+                source,
+            })?;
+        Ok(SessionFile {
+            path,
+            temporary: true,
+        })
+    }
+}
+
+impl Drop for SessionFile {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profiles() -> Profiles {
+        Profiles::parse("active = \"alice\"\n\n[profiles]\nalice = \"aaa\"\nbob = \"bbb\"\n")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_profiles_roundtrip() {
+        let mut profiles = profiles();
+        assert_eq!(profiles.active.as_deref(), Some("alice"));
+        assert_eq!(profiles.get("bob"), Some("bbb"));
+
+        profiles.set("bob", "ccc");
+        profiles.set("carol", "ddd");
+        assert_eq!(Profiles::parse(&profiles.to_toml()).unwrap(), profiles);
+        assert_eq!(profiles.get("bob"), Some("ccc"));
+
+        let mut empty = Profiles::default();
+        empty.set("dave", "eee");
+        assert_eq!(empty.active.as_deref(), Some("dave"));
+        assert!(Profiles::parse("[teams]\nx = \"y\"").is_err());
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let path = PathBuf::from("profiles.toml");
+        let loaded = |selected: Option<&str>| {
+            let path = path.clone();
+            let selected = selected.map(String::from);
+            move || Ok((profiles(), selected, path))
+        };
+        let file = Some((PathBuf::from("session"), Some("fff\n".to_string())));
+        let env = Some(("AOC_SESSION".to_string(), "eee".to_string()));
+
+        let session = resolve_with(env.clone(), loaded(Some("bob")), None);
+        assert_eq!(session.unwrap().source, Source::Env("AOC_SESSION".into()));
+
+        let session = resolve_with(None, loaded(Some("bob")), file.clone()).unwrap();
+        assert_eq!(session.token, "bbb");
+
+        let session = resolve_with(None, loaded(None), file.clone()).unwrap();
+        assert_eq!(
+            (session.token.as_str(), session.source),
+            ("fff", Source::File("session".into()))
+        );
+
+        assert!(matches!(
+            resolve_with(None, loaded(Some("zed")), file),
+            Err(SessionError::UnknownProfile { .. })
+        ));
+        assert!(matches!(
+            resolve_with(None, loaded(None), None),
+            Err(SessionError::Missing)
+        ));
+
+        // A broken profiles file only matters without a token in the environment.
+        let broken = || {
+            Err(SessionError::InvalidProfiles {
+                path: path.clone(),
+                message: "unknown key `x`.".into(),
+            })
+        };
+        assert!(resolve_with(env, broken, None).is_ok());
+        assert!(matches!(
+            resolve_with(None, broken, None),
+            Err(SessionError::InvalidProfiles { .. })
+        ));
+    }
+
+    #[test]
+    fn test_session_file() {
+        let session = Session {
+            token: "0123456789abcdef".into(),
+            source: Source::Env("AOC_SESSION".into()),
+        };
+        let (first, second) = (
+            SessionFile::new(&session).unwrap(),
+            SessionFile::new(&session).unwrap(),
+        );
+        assert_ne!(first.path, second.path);
+        assert_eq!(fs::read_to_string(&first.path).unwrap(), session.token);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first.path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let path = first.path.clone();
+        drop(first);
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_private_restricts_existing_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = env::temp_dir().join(format!("aoc-profiles-{}.toml", process::id()));
+        fs::write(&path, "active = \"alice\"\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(&path, "active = \"bob\"\n").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "active = \"bob\"\n");
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("0123456789abcdef"), "0123…cdef (16 characters)");
+        assert_eq!(mask("short"), "*****");
+    }
+}
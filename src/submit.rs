@@ -0,0 +1,456 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Answer submission for `cargo submit`: reading the site's response and keeping a log of every
+//! guess in `years/<year>/guesses/NN.jsonl`.
+//!
+//! The log is checked before anything is sent: an answer that was already rejected, that is not
+//! below a known "too high" guess or above a known "too low" guess, or that would be sent while
+//! the site still asks us to wait, is refused locally.
+use crate::json;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    Incorrect {
+        hint: Option<Hint>,
+        /// How long the site asks to wait before the next guess.
+        wait: Option<Duration>,
+    },
+    /// The answer was not checked because the previous one was too recent.
+    TooRecent {
+        wait: Duration,
+    },
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// Reads the response from the text of the answer page.
+pub fn parse_response(text: &str) -> Response {
+    if text.contains("That's the right answer") {
+        Response::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Response::Incorrect {
+            hint,
+            wait: parse_wait_minutes(text),
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Response::TooRecent {
+            wait: parse_time_left(text).unwrap_or(Duration::from_secs(60)),
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Response::WrongLevel
+    } else {
+        Response::Unknown
+    }
+}
+
+/// "please wait one minute" / "please wait 5 minutes".
+fn parse_wait_minutes(text: &str) -> Option<Duration> {
+    let lower = text.to_lowercase();
+    let rest = &lower[lower.find("please wait ")? + "please wait ".len()..];
+    let mut words = rest.split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+    words
+        .next()?
+        .starts_with("minute")
+        .then(|| Duration::from_secs(minutes * 60))
+}
+
+/// "You have 1m 22s left to wait."
+fn parse_time_left(text: &str) -> Option<Duration> {
+    let rest = &text[text.find("You have ")? + "You have ".len()..];
+    let rest = &rest[..rest.find("left to wait")?];
+
+    let mut seconds = 0;
+    for part in rest.split_whitespace() {
+        seconds += match part.split_at(part.len() - 1) {
+            (n, "m") => n.parse::<u64>().ok()? * 60,
+            (n, "s") => n.parse::<u64>().ok()?,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    TooRecent,
+    WrongLevel,
+    Unknown,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 7] = [
+        Outcome::Correct,
+        Outcome::Incorrect,
+        Outcome::TooHigh,
+        Outcome::TooLow,
+        Outcome::TooRecent,
+        Outcome::WrongLevel,
+        Outcome::Unknown,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::TooRecent => "too_recent",
+            Outcome::WrongLevel => "wrong_level",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    /// Whether the site checked the answer and rejected it.
+    pub fn is_rejected(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+}
+
+impl From<&Response> for Outcome {
+    fn from(response: &Response) -> Self {
+        match response {
+            Response::Correct => Outcome::Correct,
+            Response::Incorrect {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => Outcome::TooHigh,
+            Response::Incorrect {
+                hint: Some(Hint::TooLow),
+                ..
+            } => Outcome::TooLow,
+            Response::Incorrect { hint: None, .. } => Outcome::Incorrect,
+            Response::TooRecent { .. } => Outcome::TooRecent,
+            Response::WrongLevel => Outcome::WrongLevel,
+            Response::Unknown => Outcome::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch before which the site will not accept another answer.
+    pub retry_at: Option<u64>,
+}
+
+impl Guess {
+    pub fn new(part: u8, answer: &str, response: &Response) -> Self {
+        let timestamp = now();
+        let wait = match response {
+            Response::Incorrect { wait, .. } => *wait,
+            Response::TooRecent { wait } => Some(*wait),
+            _ => None,
+        };
+
+        Guess {
+            timestamp,
+            part,
+            answer: answer.into(),
+            outcome: response.into(),
+            retry_at: wait.map(|wait| timestamp + wait.as_secs()),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut fields = vec![
+            ("timestamp", self.timestamp.to_string()),
+            ("part", self.part.to_string()),
+            ("answer", json::escape(&self.answer)),
+            ("outcome", json::escape(self.outcome.as_str())),
+        ];
+        if let Some(retry_at) = self.retry_at {
+            fields.push(("retry_at", retry_at.to_string()));
+        }
+        json::object(&fields)
+    }
+
+    pub fn from_json(line: &str) -> Option<Self> {
+        let object = json::parse_object(line)?;
+        let outcome = object.get("outcome")?.as_str()?;
+
+        Some(Guess {
+            timestamp: object.get("timestamp")?.parse_number()?,
+            part: object.get("part")?.parse_number()?,
+            answer: object.get("answer")?.as_str()?.into(),
+            outcome: *Outcome::ALL.iter().find(|o| o.as_str() == outcome)?,
+            retry_at: object
+                .get("retry_at")
+                .and_then(|retry_at| retry_at.parse_number()),
+        })
+    }
+}
+
+/// Why an answer is not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    AlreadyRejected,
+    NotBelow { too_high: String },
+    NotAbove { too_low: String },
+    RateLimited { wait: Duration },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with {}.", answer)
+            }
+            Refusal::AlreadyRejected => write!(f, "this answer was already rejected."),
+            Refusal::NotBelow { too_high } => {
+                write!(f, "{} was too high, so this answer is too.", too_high)
+            }
+            Refusal::NotAbove { too_low } => {
+                write!(f, "{} was too low, so this answer is too.", too_low)
+            }
+            Refusal::RateLimited { wait } => write!(
+                f,
+                "the site asked to wait, try again in {}s.",
+                wait.as_secs()
+            ),
+        }
+    }
+}
+
+/// Checks an answer against earlier guesses for the day. `now` is in seconds since the epoch.
+pub fn check(log: &[Guess], part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+    if let Some(retry_at) = log.iter().filter_map(|guess| guess.retry_at).max() {
+        if retry_at > now {
+            return Err(Refusal::RateLimited {
+                wait: Duration::from_secs(retry_at - now),
+            });
+        }
+    }
+
+    let guesses: Vec<&Guess> = log.iter().filter(|guess| guess.part == part).collect();
+
+    if let Some(correct) = guesses.iter().find(|g| g.outcome == Outcome::Correct) {
+        return Err(Refusal::AlreadySolved {
+            answer: correct.answer.clone(),
+        });
+    }
+
+    if guesses
+        .iter()
+        .any(|g| g.outcome.is_rejected() && g.answer.trim() == answer.trim())
+    {
+        return Err(Refusal::AlreadyRejected);
+    }
+
+    let Ok(value) = answer.trim().parse::<i128>() else {
+        return Ok(());
+    };
+    let bound = |outcome: Outcome| {
+        guesses
+            .iter()
+            .filter(move |g| g.outcome == outcome)
+            .filter_map(|g| Some((g.answer.trim().parse::<i128>().ok()?, &g.answer)))
+    };
+
+    if let Some((_, too_high)) = bound(Outcome::TooHigh).filter(|(b, _)| value >= *b).min() {
+        return Err(Refusal::NotBelow {
+            too_high: too_high.clone(),
+        });
+    }
+    if let Some((_, too_low)) = bound(Outcome::TooLow).filter(|(b, _)| value <= *b).max() {
+        return Err(Refusal::NotAbove {
+            too_low: too_low.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+pub fn log_path(year: u16, day: u8) -> PathBuf {
+    crate::config::get()
+        .dir("guesses", year)
+        .join(format!("{:02}.jsonl", day))
+}
+
+/// Reads all guesses, skipping lines that can't be parsed. A missing file is an empty log.
+pub fn read_log(path: &Path) -> io::Result<Vec<Guess>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents.lines().filter_map(Guess::from_json).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn append(path: &Path, guess: &Guess) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", guess.to_json())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guess(part: u8, answer: &str, outcome: Outcome) -> Guess {
+        Guess {
+            timestamp: 100,
+            part,
+            answer: answer.into(),
+            outcome,
+            retry_at: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response("That's the right answer! You are one gold star closer."),
+            Response::Correct
+        );
+        assert_eq!(
+            parse_response(
+                "That's not the right answer; your answer is too high. If you're stuck, \
+                 make sure you're using the full input data. Please wait one minute before \
+                 trying again."
+            ),
+            Response::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_response(
+                "That's not the right answer. Because you have guessed incorrectly 4 times \
+                 on this puzzle, please wait 5 minutes before trying again."
+            ),
+            Response::Incorrect {
+                hint: None,
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse_response(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again. You have 1m 22s left to wait."
+            ),
+            Response::TooRecent {
+                wait: Duration::from_secs(82)
+            }
+        );
+        assert_eq!(
+            parse_response(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Response::WrongLevel
+        );
+        assert_eq!(parse_response("<html></html>"), Response::Unknown);
+    }
+
+    #[test]
+    fn test_guess_json_roundtrip() {
+        let response = Response::Incorrect {
+            hint: Some(Hint::TooLow),
+            wait: Some(Duration::from_secs(60)),
+        };
+        let guess = Guess::new(2, "42", &response);
+        assert_eq!(guess.retry_at, Some(guess.timestamp + 60));
+        assert_eq!(Guess::from_json(&guess.to_json()), Some(guess));
+    }
+
+    #[test]
+    fn test_check_bounds() {
+        let log = vec![
+            guess(1, "500", Outcome::TooHigh),
+            guess(1, "100", Outcome::TooLow),
+            guess(1, "abc", Outcome::Incorrect),
+            guess(2, "1", Outcome::TooHigh),
+        ];
+
+        assert_eq!(check(&log, 1, "300", 100), Ok(()));
+        assert_eq!(check(&log, 1, "500", 100), Err(Refusal::AlreadyRejected));
+        assert_eq!(
+            check(&log, 1, "600", 100),
+            Err(Refusal::NotBelow {
+                too_high: "500".into()
+            })
+        );
+        assert_eq!(
+            check(&log, 1, "99", 100),
+            Err(Refusal::NotAbove {
+                too_low: "100".into()
+            })
+        );
+        assert_eq!(check(&log, 1, "abc", 100), Err(Refusal::AlreadyRejected));
+        assert_eq!(check(&log, 1, "def", 100), Ok(()));
+        assert_eq!(
+            check(&log, 2, "300", 100),
+            Err(Refusal::NotBelow {
+                too_high: "1".into()
+            })
+        );
+        assert_eq!(check(&log, 2, "0", 100), Ok(()));
+    }
+
+    #[test]
+    fn test_check_solved_and_rate_limited() {
+        let mut log = vec![guess(1, "7", Outcome::Correct)];
+        assert_eq!(
+            check(&log, 1, "8", 100),
+            Err(Refusal::AlreadySolved { answer: "7".into() })
+        );
+
+        log.push(Guess {
+            retry_at: Some(160),
+            ..guess(2, "1", Outcome::TooRecent)
+        });
+        assert_eq!(
+            check(&log, 2, "1", 100),
+            Err(Refusal::RateLimited {
+                wait: Duration::from_secs(60)
+            })
+        );
+        assert_eq!(check(&log, 2, "1", 160), Ok(()));
+    }
+}
@@ -0,0 +1,185 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! The small subset of TOML used by the template's own files: `key = value` pairs where a value
+//! is an integer, a boolean, a `"string"` or a `"""multi-line string"""`, optionally grouped
+//! under `[section]` headers. Values are returned as strings; keys inside a section are
+//! returned as `section.key`.
+
+/// Parses `contents` into `(key, value)` pairs in file order.
+pub fn parse(contents: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = vec![];
+    let mut section = String::new();
+    let mut lines = contents.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            section = header
+                .split('#')
+                .next()
+                .unwrap()
+                .trim()
+                .strip_suffix(']')
+                .ok_or(format!("line {}: invalid section header.", index + 1))?
+                .trim()
+                .to_string();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(format!("line {}: expected `key = value`.", index + 1))?;
+        let key = key.trim();
+        let value = value.trim();
+
+        let parsed = if let Some(rest) = value.strip_prefix("\"\"\"") {
+            parse_multiline(rest, &mut lines.by_ref().map(|(_, line)| line))
+                .ok_or(format!("line {}: unterminated `\"\"\"` string.", index + 1))?
+        } else if let Some(rest) = value.strip_prefix('"') {
+            parse_basic_string(rest).ok_or(format!("line {}: invalid string value.", index + 1))?
+        } else {
+            let value = value.split('#').next().unwrap().trim();
+            if value == "true" || value == "false" {
+                value.to_string()
+            } else {
+                value
+                    .parse::<i64>()
+                    .map_err(|_| {
+                        format!("line {}: expected a string, integer or boolean.", index + 1)
+                    })?
+                    .to_string()
+            }
+        };
+
+        if key.is_empty() {
+            return Err(format!("line {}: missing key.", index + 1));
+        }
+
+        let key = if section.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", section, key)
+        };
+        pairs.push((key, parsed));
+    }
+
+    Ok(pairs)
+}
+
+/// Quotes a value for writing back to a file.
+pub fn quote(value: &str) -> String {
+    if value.contains('\n') {
+        format!("\"\"\"\n{}\"\"\"", value)
+    } else {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("\"{}\"", escaped)
+    }
+}
+
+fn parse_basic_string(rest: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = rest.chars();
+    loop {
+        match chars.next()? {
+            '"' => break,
+            '\\' => match chars.next()? {
+                'n' => out.push('\n'),
+                't' => out.push('\t'),
+                '"' => out.push('"'),
+                '\\' => out.push('\\'),
+                _ => return None,
+            },
+            c => out.push(c),
+        }
+    }
+
+    let trailing = chars.as_str().trim();
+    (trailing.is_empty() || trailing.starts_with('#')).then_some(out)
+}
+
+/// Collects a `"""` string. A newline directly after the opening quotes is dropped, as in TOML.
+fn parse_multiline<'a>(first: &str, lines: &mut impl Iterator<Item = &'a str>) -> Option<String> {
+    if let Some(end) = first.find("\"\"\"") {
+        return Some(first[..end].to_string());
+    }
+
+    let mut parts = vec![];
+    if !first.is_empty() {
+        parts.push(first.to_string());
+    }
+
+    for line in lines {
+        if let Some(end) = line.find("\"\"\"") {
+            parts.push(line[..end].to_string());
+            return Some(parts.join("\n"));
+        }
+        parts.push(line.to_string());
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(contents: &str) -> Vec<(String, String)> {
+        parse(contents).unwrap()
+    }
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(
+            pairs("# comment\na = 24000\nb = \"x \\\"y\\\"\" # note\nc = true\n"),
+            vec![
+                ("a".into(), "24000".into()),
+                ("b".into(), "x \"y\"".into()),
+                ("c".into(), "true".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_multiline() {
+        assert_eq!(
+            pairs("art = \"\"\"\n##..\n#..#\n\"\"\"\nafter = 1"),
+            vec![
+                ("art".into(), "##..\n#..#\n".into()),
+                ("after".into(), "1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_sections() {
+        assert_eq!(
+            pairs("year = 2022\n[dirs]\ninputs = \"data\"\n"),
+            vec![
+                ("year".into(), "2022".into()),
+                ("dirs.inputs".into(), "data".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("part1 24000").is_err());
+        assert!(parse("part1 = abc").is_err());
+        assert!(parse("[unclosed").is_err());
+        assert!(parse("art = \"\"\"\nunterminated").is_err());
+    }
+
+    #[test]
+    fn test_quote_roundtrip() {
+        for value in ["plain", "with \"quotes\" and \\", "##..\n#..#\n"] {
+            let parsed = pairs(&format!("v = {}", quote(value)));
+            assert_eq!(parsed[0].1, value);
+        }
+    }
+}
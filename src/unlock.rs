@@ -0,0 +1,78 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! When puzzles unlock. A day's puzzle goes live at midnight US Eastern time (UTC-5, there is no
+//! daylight saving time in December) on that day of December.
+use std::time::Duration;
+
+const UNLOCK_HOUR_UTC: u64 = 5;
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The number of days between 1970-01-01 and the given date (proleptic Gregorian calendar).
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The unlock instant of a puzzle as seconds since the Unix epoch.
+pub fn unlock_time(year: u16, day: u8) -> u64 {
+    let days = days_from_civil(i64::from(year), 12, u32::from(day)) as u64;
+    days * 86400 + UNLOCK_HOUR_UTC * 3600
+}
+
+/// How long to wait before retrying a download that failed because the puzzle is not
+/// available yet: 1s, 2s, 4s, ... up to a minute.
+pub fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.min(6)).min(MAX_BACKOFF)
+}
+
+/// "2d 03:04:05", or "03:04:05" when less than a day is left.
+pub fn format_countdown(seconds: u64) -> String {
+    let (days, rest) = (seconds / 86400, seconds % 86400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{}d {}", days, time)
+    } else {
+        time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(unlock_time(2022, 1), 1669870800);
+        // 2024-12-25T05:00:00Z
+        assert_eq!(unlock_time(2024, 25), 1735102800);
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let delays: Vec<u64> = (0..8).map(|attempt| backoff(attempt).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(59), "00:00:59");
+        assert_eq!(format_countdown(3 * 3600 + 4 * 60 + 5), "03:04:05");
+        assert_eq!(format_countdown(2 * 86400 + 61), "2d 00:01:01");
+    }
+}
@@ -52,9 +52,8 @@ fn find_badge(lines: &[&str]) -> Option<char> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 3);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]
@@ -64,9 +64,8 @@ fn parse_line(line: &str) -> ([u32; 2], [u32; 2]) {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 4);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]
@@ -165,9 +165,8 @@ fn execute_instructions(input_crates: &Vec<Vec<char>>, instructions: Vec<Instruc
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 5);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]
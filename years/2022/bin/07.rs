@@ -201,9 +201,8 @@ fn get_direct_decendants(keys: &Vec<&String>, directory: &str) -> Vec<String> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 7);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]
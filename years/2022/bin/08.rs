@@ -0,0 +1,74 @@
+use advent_of_code::helpers::grid::DIRECTIONS_4;
+use advent_of_code::helpers::{Grid, Pos};
+
+pub fn part_one(input: &str) -> Option<u32> {
+    // Read grid from the disk
+    let grid = Grid::from_digits(input).ok()?;
+
+    // A tree is visible if every tree between it and an edge is shorter
+    let visible_trees = grid
+        .positions()
+        .filter(|&tree| is_tree_visible(&grid, tree))
+        .count();
+
+    Some(visible_trees as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    // Read grid from the disk
+    let grid = Grid::from_digits(input).ok()?;
+
+    grid.positions()
+        .map(|tree| calculate_scenic_score(&grid, tree))
+        .max()
+}
+
+fn calculate_scenic_score(grid: &Grid<u8>, tree: Pos) -> u32 {
+    let tree_height = grid[tree];
+
+    DIRECTIONS_4
+        .into_iter()
+        .map(|direction| {
+            // Count trees up to and including the first one at least as tall
+            let mut viewing_distance = 0;
+            for (_, &height) in grid.ray(tree, direction) {
+                viewing_distance += 1;
+                if height >= tree_height {
+                    break;
+                }
+            }
+            viewing_distance
+        })
+        .product()
+}
+
+fn is_tree_visible(grid: &Grid<u8>, tree: Pos) -> bool {
+    let tree_height = grid[tree];
+
+    DIRECTIONS_4.into_iter().any(|direction| {
+        grid.ray(tree, direction)
+            .all(|(_, &height)| height < tree_height)
+    })
+}
+
+fn main() {
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 8);
+        assert_eq!(part_one(&input), Some(21_u32));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 8);
+        assert_eq!(part_two(&input), Some(8_u32));
+    }
+}
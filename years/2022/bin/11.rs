@@ -1,3 +1,4 @@
+use advent_of_code::helpers::parse::{captures, parse_blocks, Field, ParseError};
 use std::cell::RefCell;
 
 /*
@@ -9,6 +10,13 @@ Monkey 0:
     If true: throw to monkey 2
     If false: throw to monkey 3
  */
+const MONKEY: &str = "Monkey {}:
+  Starting items: {}
+  Operation: new = {} {} {}
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey {}";
+
 #[derive(PartialEq, PartialOrd, Debug, Clone)]
 struct Monkey {
     items: RefCell<Vec<u64>>,
@@ -88,7 +96,7 @@ struct Test {
 
 pub fn part_one(input: &str) -> Option<u64> {
     // Read monkeys
-    let (monkeys, lcm) = read_monkeys(input)?;
+    let (monkeys, lcm) = read_monkeys(input).ok()?;
     // Perform throws and count inspections
     let mut inspection_counts = count_inspects(monkeys, 20, 3, lcm);
     // Sort inspections
@@ -99,7 +107,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 
 pub fn part_two(input: &str) -> Option<u64> {
     // Read monkeys
-    let (monkeys, lcm) = read_monkeys(input)?;
+    let (monkeys, lcm) = read_monkeys(input).ok()?;
     // Perform throws and count inspections
     let mut inspection_counts = count_inspects(monkeys, 10000, 1, lcm);
     // Sort inspections
@@ -147,98 +155,61 @@ fn count_inspects(mut monkeys: Vec<Monkey>, rounds: usize, round_divisor: u64, l
     inspection_counts
 }
 
-fn read_monkeys(input: &str) -> Option<(Vec<Monkey>, u64)> {
-    let mut lcm = 1u64;
-
-    let split: Vec<&str> = input.lines().collect();
-    let monkey_groups = split.chunks(7);
-
-    let mut monkeys: Vec<Monkey> = vec![];
-
-    for monkey_group in monkey_groups {
-
-        let starting_items: Vec<&str> = monkey_group
-            .get(1)?
-            .split("Starting items:")
-            .collect();
-        let starting_items: Vec<u64> = starting_items
-            .last()?
-            .trim()
-            .split(", ")
-            .map(|x| x.parse::<u64>().expect("Failed to interpret starting item"))
-            .collect();
-
-        let operation_expr = monkey_group
-            .get(2)?
-            .split("Operation: new = ")
-            .last()?;
-        let operation_parts: Vec<&str> = operation_expr.split(' ').collect();
-        let monkey_operation = Expression{
-            left_hand_side: match *operation_parts.first()? {
-                "old" => OperationTerm::Old,
-                constant => OperationTerm::Constant(
-                    constant.parse::<u64>()
-                        .expect("Failed to interpret constant")
-                )
-            },
-            operation: match *operation_parts.get(1)? {
-                "+" => Operation::Plus,
-                "*" => Operation::Times,
-                _ => {
-                    println!("Failed to interpret operation");
-                    return None;
-                }
-            },
-            right_hand_side: match *operation_parts.get(2)? {
-                "old" => OperationTerm::Old,
-                constant => OperationTerm::Constant(
-                    constant.parse::<u64>()
-                        .expect("Failed to interpret constant")
-                )
-            },
-        };
+fn read_monkeys(input: &str) -> Result<(Vec<Monkey>, u64), ParseError> {
+    let monkeys = parse_blocks(input, read_monkey)?;
+    let lcm = monkeys
+        .iter()
+        .fold(1, |lcm, monkey| find_lcm(lcm, monkey.test.divisible_by));
 
-        let divisible_by: Vec<&str> = (*monkey_group.get(3)?)
-            .split(' ')
-            .collect();
-        let divisible_by = divisible_by
-            .last()?
-            .parse::<u64>()
-            .expect("Failed to interpret test -> divisible by");
-        lcm = find_lcm(lcm, divisible_by);
-
-        let true_throw: Vec<&str> = (*monkey_group.get(4)?)
-            .split(' ')
-            .collect();
-        let true_throw = true_throw
-            .last()?
-            .parse::<usize>()
-            .expect("Failed to interpret test -> true throw");
-
-        let false_throw: Vec<&str> = (*monkey_group.get(5)?)
-            .split(' ')
-            .collect();
-        let false_throw = false_throw
-            .last()?
-            .parse::<usize>()
-            .expect("Failed to interpret test -> false throw");
-
-        let monkey_test = Test{
-            divisible_by,
-            r#true: true_throw,
-            r#false: false_throw,
-        };
+    Ok((monkeys, lcm))
+}
 
-        let monkey = Monkey{
-            items: RefCell::new(starting_items),
-            operation: monkey_operation,
-            test: monkey_test,
-        };
-        
-        monkeys.push(monkey);
-    }
+fn read_monkey(block: &str) -> Result<Monkey, ParseError> {
+    let [
+        _,
+        starting_items,
+        left_hand_side,
+        operation,
+        right_hand_side,
+        divisible_by,
+        true_throw,
+        false_throw,
+    ] = captures(MONKEY, block)?;
+
+    let monkey_operation = Expression {
+        left_hand_side: read_operation_term(left_hand_side)?,
+        operation: match operation.as_str() {
+            "+" => Operation::Plus,
+            "*" => Operation::Times,
+            _ => return Err(operation.error("expected `+` or `*`")),
+        },
+        right_hand_side: read_operation_term(right_hand_side)?,
+    };
+
+    let monkey_test = Test {
+        divisible_by: divisible_by.parse()?,
+        r#true: true_throw.parse()?,
+        r#false: false_throw.parse()?,
+    };
+
+    let items = starting_items
+        .split(", ")
+        .iter()
+        .map(Field::parse)
+        .collect::<Result<_, _>>()?;
+
+    Ok(Monkey {
+        items: RefCell::new(items),
+        operation: monkey_operation,
+        test: monkey_test,
+    })
+}
 
-    Some((monkeys, lcm))
+fn read_operation_term(term: Field) -> Result<OperationTerm, ParseError> {
+    match term.as_str() {
+        "old" => Ok(OperationTerm::Old),
+        _ => term.parse().map(OperationTerm::Constant),
+    }
 }
 
 // LCM and GCD via https://rustp.org/number-theory/lcm/
@@ -260,9 +231,8 @@ fn find_lcm(a:u64, b:u64) -> u64{
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 11);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]
@@ -280,4 +250,11 @@ mod tests {
         let input = advent_of_code::read_file("examples", 11);
         assert_eq!(part_two(&input), Some(2713310158_u64));
     }
+
+    #[test]
+    fn test_read_monkeys_rejects_bad_items() {
+        let input = advent_of_code::read_file("examples", 11).replacen("79, 98", "79, 9x8", 1);
+        let error = read_monkeys(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 23));
+    }
 }
@@ -0,0 +1,79 @@
+use advent_of_code::helpers::search::bfs;
+use advent_of_code::helpers::{Grid, Pos};
+
+const MAX_STEP_UP: i32 = 1;
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let (topology, starting, ending) = read_input(input)?;
+    let paths = bfs([starting], |&tile| climbable_neighbours(&topology, tile));
+    let path_size = paths.distance(&ending)?;
+
+    Some(path_size as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let (topology, _, ending) = read_input(input)?;
+
+    // Start from all the lowest points at once, the first to reach the end wins
+    let min_elevation_points = topology
+        .iter()
+        .filter(|(_, &elevation)| elevation == 0)
+        .map(|(point, _)| point);
+    let paths = bfs(min_elevation_points, |&tile| climbable_neighbours(&topology, tile));
+    let path_size = paths.distance(&ending)?;
+
+    Some(path_size as u32)
+}
+
+fn climbable_neighbours(topology: &Grid<i32>, tile: Pos) -> impl Iterator<Item = Pos> + '_ {
+    let tile_elevation = topology[tile];
+    topology
+        .neighbours_4(tile)
+        .filter(move |&neighbour| topology[neighbour] - tile_elevation <= MAX_STEP_UP)
+}
+
+fn read_input(input: &str) -> Option<(Grid<i32>, Pos, Pos)> {
+    let tiles = Grid::from_chars(input).ok()?;
+    let starting = tiles.position(|&tile| tile == 'S')?;
+    let ending = tiles.position(|&tile| tile == 'E')?;
+    let topology = Grid::parse(input, char_height).ok()?;
+
+    Some((topology, starting, ending))
+}
+
+fn char_height(character: char) -> Option<i32> {
+    match character {
+        'S' => Some(0),
+        'E' => Some(25),
+        character => {
+            let possible_answer = (character as i32) - ('a' as i32);
+            if possible_answer <= 25 {
+                Some(possible_answer)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn main() {
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 12);
+        assert_eq!(part_one(&input), Some(31_u32));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 12);
+        assert_eq!(part_two(&input), Some(29_u32));
+    }
+}
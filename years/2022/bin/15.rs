@@ -0,0 +1,146 @@
+use advent_of_code::helpers::interval::IntervalSet;
+use advent_of_code::helpers::parse::{parse_lines, scan, ParseError};
+use std::collections::HashSet;
+
+advent_of_code::params! {
+    pub struct Params {
+        /// The row whose coverage is counted in part one.
+        row: i32 = 2000000,
+        /// The largest x and y coordinate searched in part two.
+        upper_bound: i32 = 4000000,
+    }
+}
+
+const SENSOR: &str = "Sensor at x={}, y={}: closest beacon is at x={}, y={}";
+
+#[derive(Debug, Clone)]
+struct BeaconSensor {
+    sensor_pos: [i32; 2],
+    nearest_beacon: [i32; 2],
+}
+
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
+    let sensors = read_input(input).ok()?;
+    let coverage = determine_one_row_coverage(&sensors, params.row);
+
+    // Known beacons can't be where the distress beacon is, but they aren't "no beacon" tiles either
+    let beacons_on_row: HashSet<i32> = sensors
+        .iter()
+        .map(|sensor| sensor.nearest_beacon)
+        .filter(|beacon| beacon[1] == params.row && coverage.contains(beacon[0]))
+        .map(|beacon| beacon[0])
+        .collect();
+
+    Some((coverage.len() - beacons_on_row.len() as i32) as u32)
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<u64> {
+    let sensors = read_input(input).ok()?;
+    let [x, y] = find_distress_beacon(&sensors, params.upper_bound as i64)?;
+
+    Some(4000000 * x as u64 + y as u64)
+}
+
+impl BeaconSensor {
+    fn manhattan_distance(&self) -> i32 {
+        (self.sensor_pos[0] - self.nearest_beacon[0]).abs()
+            + (self.sensor_pos[1] - self.nearest_beacon[1]).abs()
+    }
+}
+
+// Returns the tiles of the row covered by at least one sensor.
+fn determine_one_row_coverage(beacon_sensors: &[BeaconSensor], row: i32) -> IntervalSet<i32> {
+    beacon_sensors
+        .iter()
+        .filter_map(|beacon_sensor| {
+            let sensor_loc = beacon_sensor.sensor_pos;
+            let vertical_distance_from_row = (sensor_loc[1] - row).abs();
+
+            // We are too far from the row - no coverage.
+            let distance_after_reaching_row =
+                beacon_sensor.manhattan_distance() - vertical_distance_from_row;
+            if distance_after_reaching_row < 0 {
+                return None;
+            }
+
+            Some(
+                sensor_loc[0] - distance_after_reaching_row
+                    ..sensor_loc[0] + distance_after_reaching_row + 1,
+            )
+        })
+        .collect()
+}
+
+// Turned by 45 degrees, to u = x + y and v = x - y, the area a sensor covers is a square.
+// The distress beacon is the only uncovered tile, so the tile next to it is covered by some
+// sensor, which puts its u or v just outside of that sensor's square. For each of these
+// candidate lines, the tiles the other squares leave uncovered along it are gaps in an
+// interval set.
+fn find_distress_beacon(sensors: &[BeaconSensor], upper_bound: i64) -> Option<[i64; 2]> {
+    let squares: Vec<([i64; 2], i64)> = sensors
+        .iter()
+        .map(|sensor| {
+            let [x, y] = sensor.sensor_pos.map(i64::from);
+            ([x + y, x - y], i64::from(sensor.manhattan_distance()))
+        })
+        .collect();
+
+    for axis in 0..2 {
+        let other_axis = 1 - axis;
+        for (center, range) in &squares {
+            for fixed in [center[axis] - range - 1, center[axis] + range + 1] {
+                let coverage: IntervalSet<i64> = squares
+                    .iter()
+                    .filter(|(center, range)| (center[axis] - fixed).abs() <= *range)
+                    .map(|(center, range)| {
+                        center[other_axis] - range..center[other_axis] + range + 1
+                    })
+                    .collect();
+
+                // 0 <= x, y <= upper_bound along the line, with x = (u + v) / 2, y = (u - v) / 2
+                let bounds = if axis == 0 {
+                    (-fixed).max(fixed - 2 * upper_bound)..fixed.min(2 * upper_bound - fixed) + 1
+                } else {
+                    fixed.max(-fixed)..(2 * upper_bound - fixed).min(2 * upper_bound + fixed) + 1
+                };
+
+                for gap in coverage.gaps(bounds) {
+                    // u and v of a tile are both even or both odd
+                    let other = gap.start + (gap.start + fixed).rem_euclid(2);
+                    if other < gap.end {
+                        let [u, v] = if axis == 0 {
+                            [fixed, other]
+                        } else {
+                            [other, fixed]
+                        };
+                        return Some([(u + v) / 2, (u - v) / 2]);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn read_input(input: &str) -> Result<Vec<BeaconSensor>, ParseError> {
+    parse_lines(input, |line| {
+        let [sensor_x, sensor_y, beacon_x, beacon_y] = scan(SENSOR, line)?;
+        Ok(BeaconSensor {
+            sensor_pos: [sensor_x, sensor_y],
+            nearest_beacon: [beacon_x, beacon_y],
+        })
+    })
+}
+
+fn main() {
+    advent_of_code::solve!(1, part_one, params: Params);
+    advent_of_code::solve!(2, part_two, params: Params);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!(15, [example], params: Params);
+}
@@ -322,9 +322,8 @@ fn find_zero_valves(valves: &HashMap<String, Valve>) -> Vec<String> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 16);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]
@@ -0,0 +1,266 @@
+const NUM_ROCKS_1: usize = 2022;
+const NUM_ROCKS_2: usize = 1000000000000;
+// 0 means that rocks start at the same y index as the max rock.
+// Set to 3 to start rocks 3 spaces above the highest rock.
+const DROP_HEIGHT: usize = 3;
+
+// Each row of the column is a bitmask of its 7 spaces, the left wall side in bit 6.
+const LEFT_SPACE: u8 = 0b1000000;
+const RIGHT_SPACE: u8 = 0b0000001;
+const FULL_ROW: u8 = 0b1111111;
+
+// The pieces as bitmasks of their rows from the bottom up, starting two spaces away from the
+// left wall. Unused rows are 0.
+const PIECES: [[u8; 4]; 5] = [
+    // ####
+    [0b0011110, 0, 0, 0],
+    // .#.
+    // ###
+    // .#.
+    [0b0001000, 0b0011100, 0b0001000, 0],
+    // ..#
+    // ..#
+    // ###
+    [0b0011100, 0b0000100, 0b0000100, 0],
+    // #
+    // #
+    // #
+    // #
+    [0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    // ##
+    // ##
+    [0b0011000, 0b0011000, 0, 0],
+];
+
+use advent_of_code::helpers::cycle::CycleDetector;
+use std::fmt;
+
+#[derive(Clone, Debug)]
+enum Jet {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Debug)]
+struct Column {
+    // Settled rocks from the lowest row still kept up to the highest rock
+    rows: Vec<u8>,
+    // Rows below `rows` that no piece can reach anymore
+    pruned_rows: u64,
+}
+
+impl Column {
+    fn new() -> Self {
+        Self {
+            rows: vec![],
+            pruned_rows: 0,
+        }
+    }
+
+    fn highest_rock(&self) -> u64 {
+        self.pruned_rows + self.rows.len() as u64
+    }
+
+    fn collides(&self, piece: &[u8; 4], y: usize) -> bool {
+        piece
+            .iter()
+            .zip(self.rows.iter().skip(y))
+            .any(|(piece_row, row)| piece_row & row != 0)
+    }
+
+    // Drops piece number `piece_num`, pushed by the jets from `jet_index` on, and returns the
+    // index of the next jet.
+    fn drop_piece(&mut self, piece_num: usize, jets: &[Jet], mut jet_index: usize) -> usize {
+        let mut piece = PIECES[piece_num % PIECES.len()];
+        let mut y = self.rows.len() + DROP_HEIGHT;
+
+        loop {
+            // Get pushed by the jet, unless a wall or a rock is in the way
+            let pushed = match jets[jet_index % jets.len()] {
+                Jet::Left if piece.iter().all(|row| row & LEFT_SPACE == 0) => {
+                    piece.map(|row| row << 1)
+                }
+                Jet::Right if piece.iter().all(|row| row & RIGHT_SPACE == 0) => {
+                    piece.map(|row| row >> 1)
+                }
+                _ => piece,
+            };
+            jet_index += 1;
+            if !self.collides(&pushed, y) {
+                piece = pushed;
+            }
+
+            // Fall, or come to rest on the floor or a rock
+            if y == 0 || self.collides(&piece, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        for (offset, piece_row) in piece.into_iter().enumerate().filter(|(_, row)| *row != 0) {
+            match self.rows.get_mut(y + offset) {
+                Some(row) => *row |= piece_row,
+                None => self.rows.push(piece_row),
+            }
+        }
+        self.prune();
+
+        jet_index
+    }
+
+    // Drops the rows below the reachable surface. Going from the top down, a space can be reached
+    // if it is empty and the space above it or one next to it can be reached. Pieces can't get
+    // past the row below the lowest reachable space, so the rows under that one never matter again.
+    fn prune(&mut self) {
+        let mut reachable = FULL_ROW;
+
+        for y in (0..self.rows.len()).rev() {
+            let empty = !self.rows[y] & FULL_ROW;
+            reachable &= empty;
+            loop {
+                let spread = (reachable | reachable << 1 | reachable >> 1) & empty;
+                if spread == reachable {
+                    break;
+                }
+                reachable = spread;
+            }
+
+            if reachable == 0 {
+                self.pruned_rows += y as u64;
+                self.rows.drain(..y);
+                return;
+            }
+        }
+    }
+
+    // The state the next pieces fall into: the piece and jet come from the caller, the surface
+    // is what is left of the column after pruning.
+    fn surface(&self) -> Vec<u8> {
+        self.rows.clone()
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Tower of height {}:", self.highest_rock())?;
+
+        for row in self.rows.iter().rev() {
+            let spaces: String = (0..7)
+                .rev()
+                .map(|space| if row & (1 << space) != 0 { '#' } else { '.' })
+                .collect();
+            writeln!(f, "|{}|", spaces)?;
+        }
+
+        // The floor, or the rows that were pruned
+        if self.pruned_rows == 0 {
+            writeln!(f, "+-------+")
+        } else {
+            writeln!(f, "~~~~~~~~~ {} rows below", self.pruned_rows)
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let jets = read_input(input)?;
+    Some(calculate_tower_height(&jets, NUM_ROCKS_1) as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let jets = read_input(input)?;
+    Some(calculate_tower_height_by_cycles(&jets, NUM_ROCKS_2))
+}
+
+fn calculate_tower_height(jets: &[Jet], num_rocks: usize) -> u64 {
+    let mut column = Column::new();
+    let mut jet_index: usize = 0;
+
+    for piece_num in 0..num_rocks {
+        jet_index = column.drop_piece(piece_num, jets, jet_index);
+    }
+
+    column.highest_rock()
+}
+
+fn calculate_tower_height_by_cycles(jets: &[Jet], num_rocks: usize) -> u64 {
+    let mut column = Column::new();
+    let mut jet_index: usize = 0;
+    let mut heights: Vec<u64> = vec![];
+    let mut detector = CycleDetector::new();
+
+    for piece_num in 0..num_rocks {
+        heights.push(column.highest_rock());
+
+        // The next piece, the next jet and the surface decide how the tower grows from here
+        let state = (
+            piece_num % PIECES.len(),
+            jet_index % jets.len(),
+            column.surface(),
+        );
+        if let Some(cycle) = detector.observe(state) {
+            return cycle.extrapolate(num_rocks, |rocks| heights[rocks]);
+        }
+
+        jet_index = column.drop_piece(piece_num, jets, jet_index);
+    }
+
+    column.highest_rock()
+}
+
+fn read_input(input: &str) -> Option<Vec<Jet>> {
+    let mut jets: Vec<Jet> = vec![];
+
+    for c in input.trim().chars() {
+        jets.push(match c {
+            '<' => Jet::Left,
+            '>' => Jet::Right,
+            _ => {
+                println!("Invalid jet direction read: '{}'", c);
+                return None;
+            }
+        });
+    }
+
+    Some(jets)
+}
+
+fn main() {
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 17);
+        assert_eq!(part_one(&input), Some(3068_u32));
+
+        // The part two solver has to agree with dropping every piece
+        let jets = read_input(&input).unwrap();
+        assert_eq!(
+            calculate_tower_height_by_cycles(&jets, NUM_ROCKS_1),
+            3068_u64
+        );
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 17);
+        assert_eq!(part_two(&input), Some(1514285714288_u64));
+    }
+
+    #[test]
+    fn test_pruning() {
+        let jets = read_input(&advent_of_code::read_file("examples", 17)).unwrap();
+        let mut column = Column::new();
+        let mut jet_index = 0;
+        for piece_num in 0..NUM_ROCKS_1 {
+            jet_index = column.drop_piece(piece_num, &jets, jet_index);
+            assert!(column.rows.len() < 100, "{}", column);
+        }
+        assert!(column.pruned_rows > 0);
+    }
+}
@@ -246,9 +246,8 @@ fn read_input(input: &str) -> Option<Vec<(u32, u32, u32)>> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 18);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]
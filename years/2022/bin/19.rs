@@ -0,0 +1,61 @@
+use advent_of_code::helpers::parse::{parse_lines, scan, ParseError};
+
+const BLUEPRINT: &str = "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+    Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.";
+
+struct Blueprint {
+    ore_cost: u32,
+    clay_cost: u32,
+    obsidian_cost_ore: u32,
+    obsidian_cost_clay: u32,
+    geode_cost_ore: u32,
+    geode_cost_obsidian: u32,
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let _blueprints = read_input(input).ok()?;
+
+    None
+}
+
+pub fn part_two(_input: &str) -> Option<u32> {
+    None
+}
+
+fn read_input(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    parse_lines(input, |line| {
+        let [_, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+            scan(BLUEPRINT, line)?;
+
+        Ok(Blueprint {
+            ore_cost: ore,
+            clay_cost: clay,
+            obsidian_cost_ore: obsidian_ore,
+            obsidian_cost_clay: obsidian_clay,
+            geode_cost_ore: geode_ore,
+            geode_cost_obsidian: geode_obsidian,
+        })
+    })
+}
+
+fn main() {
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 19);
+        assert_eq!(part_one(&input), Some(12_u32));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 19);
+        assert_eq!(part_two(&input), None);
+    }
+}
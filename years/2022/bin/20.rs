@@ -120,9 +120,8 @@ fn read_input(input: &str) -> Result<Vec<i32>, std::num::ParseIntError> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 20);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, part_one);
+    advent_of_code::solve!(2, part_two);
 }
 
 #[cfg(test)]